@@ -0,0 +1,237 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::default_input;
+use crate::read_input;
+use crate::solve;
+use crate::SOLUTIONS;
+
+// Every benchmark is sampled until it used up this much time, within the
+// sample limits below.
+const MEASUREMENT_TIME: Duration = Duration::from_secs(3);
+const MIN_SAMPLES: usize = 3;
+const MAX_SAMPLES: usize = 100;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Measurement {
+    pub day: usize,
+    pub part: u32,
+    pub samples: usize,
+    pub median: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+pub fn results_directory() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.push("target");
+    path.push("bench");
+
+    path
+}
+
+pub fn measure(day: usize, part: u32, input: &str) -> Measurement {
+    let solution = SOLUTIONS[day - 1];
+
+    // the first run warms up caches and the allocator and tells how many
+    // samples fit into the measurement time
+    let (_, warm_up) = solve(solution, part, input);
+    let fitting = MEASUREMENT_TIME.as_nanos() / std::cmp::max(warm_up.as_nanos(), 1);
+    let samples = (fitting as usize).clamp(MIN_SAMPLES, MAX_SAMPLES);
+
+    let mut times = (0..samples).map(|_| solve(solution, part, input).1).collect::<Vec<Duration>>();
+    times.sort();
+
+    let median = if samples.is_multiple_of(2) {
+        (times[samples / 2 - 1] + times[samples / 2]) / 2
+    } else {
+        times[samples / 2]
+    };
+
+    Measurement {
+        day,
+        part,
+        samples,
+        median,
+        mean: times.iter().sum::<Duration>() / samples as u32,
+        min: times[0],
+        max: times[samples - 1],
+    }
+}
+
+pub fn run(days: &[usize]) -> Result<Vec<Measurement>, String> {
+    let mut measurements = Vec::new();
+    for &day in days {
+        let path = default_input(day);
+        let input = read_input(&path)?;
+        SOLUTIONS[day - 1].check(&input).map_err(|error| format!("{}: {}", path.display(), error))?;
+        for part in 1..=2 {
+            let measurement = measure(day, part, &input);
+            println!(
+                "day {:02} part {} {:>12.3?}  [{:.3?} .. {:.3?}]  {} samples",
+                day, part, measurement.median, measurement.min, measurement.max, measurement.samples
+            );
+            measurements.push(measurement);
+        }
+    }
+
+    Ok(measurements)
+}
+
+// One measurement per line, so that `from_json` can read the files back
+// without a full JSON parser.
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let lines = measurements.iter().map(|measurement| {
+        format!(
+            "  {{\"day\": {}, \"part\": {}, \"samples\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"min_ns\": {}, \"max_ns\": {}}}",
+            measurement.day,
+            measurement.part,
+            measurement.samples,
+            measurement.median.as_nanos(),
+            measurement.mean.as_nanos(),
+            measurement.min.as_nanos(),
+            measurement.max.as_nanos(),
+        )
+    }).collect::<Vec<String>>();
+
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+fn field(line: &str, name: &str) -> Option<u64> {
+    let start = line.find(&format!("\"{}\":", name))? + name.len() + 3;
+    let value = line[start..].trim_start();
+    let end = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+
+    value[..end].parse::<u64>().ok()
+}
+
+pub fn from_json(text: &str) -> Result<Vec<Measurement>, String> {
+    let mut measurements = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            continue;
+        }
+
+        let measurement = (|| Some(Measurement {
+            day: field(line, "day")? as usize,
+            part: field(line, "part")? as u32,
+            samples: field(line, "samples")? as usize,
+            median: Duration::from_nanos(field(line, "median_ns")?),
+            mean: Duration::from_nanos(field(line, "mean_ns")?),
+            min: Duration::from_nanos(field(line, "min_ns")?),
+            max: Duration::from_nanos(field(line, "max_ns")?),
+        }))();
+
+        match measurement {
+            Some(measurement) => measurements.push(measurement),
+            None => return Err(format!("line {}: not a measurement: {}", index + 1, line)),
+        }
+    }
+
+    Ok(measurements)
+}
+
+pub fn load(path: &PathBuf) -> Result<Vec<Measurement>, String> {
+    from_json(&read_input(path)?)
+}
+
+pub fn save(path: &PathBuf, measurements: &[Measurement]) -> Result<(), String> {
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).map_err(|error| format!("Could not create {}: {}", directory.display(), error))?;
+    }
+
+    std::fs::write(path, to_json(measurements)).map_err(|error| format!("Could not write {}: {}", path.display(), error))
+}
+
+// Compares the medians against the baseline and returns the report together
+// with the number of regressions. Changes within `threshold` percent are
+// treated as noise.
+pub fn compare(measurements: &[Measurement], baseline: &[Measurement], threshold: f64) -> (String, usize) {
+    let mut report = String::new();
+    let mut regressions = 0;
+    for measurement in measurements {
+        let before = baseline.iter().find(|before| before.day == measurement.day && before.part == measurement.part);
+        let line = match before {
+            // a zero baseline is below the timer's resolution and has no
+            // percentage to hold against the threshold
+            Some(before) if before.median.is_zero() => {
+                format!("{:>12.3?} -> {:>12.3?}  {:>9}  not comparable", before.median, measurement.median, "n/a")
+            }
+            Some(before) => {
+                let change = 100.0 * (measurement.median.as_secs_f64() / before.median.as_secs_f64() - 1.0);
+                let verdict = if change > threshold {
+                    regressions += 1;
+                    "REGRESSED"
+                } else if change < -threshold {
+                    "improved"
+                } else {
+                    "no change"
+                };
+
+                format!("{:>12.3?} -> {:>12.3?}  {:>+8.1}%  {}", before.median, measurement.median, change, verdict)
+            }
+            None => format!("{:>12} -> {:>12.3?}  new", "", measurement.median),
+        };
+
+        report.push_str(&format!("day {:02} part {} {}\n", measurement.day, measurement.part, line));
+    }
+
+    (report, regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(day: usize, part: u32, median: u64) -> Measurement {
+        Measurement {
+            day,
+            part,
+            samples: 5,
+            median: Duration::from_nanos(median),
+            mean: Duration::from_nanos(median + 7),
+            min: Duration::from_nanos(median / 2),
+            max: Duration::from_nanos(median * 2),
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let measurements = vec![measurement(1, 1, 1_500), measurement(25, 2, 123_456_789_012)];
+
+        assert_eq!(from_json(&to_json(&measurements)), Ok(measurements));
+        assert_eq!(from_json("[\n]\n"), Ok(Vec::new()));
+        assert!(from_json("[\n  {\"day\": 1}\n]\n").is_err());
+    }
+
+    #[test]
+    fn compare_flags_regressions() {
+        let baseline = vec![measurement(1, 1, 1_000), measurement(1, 2, 1_000), measurement(2, 1, 1_000)];
+        let current = vec![measurement(1, 1, 1_050), measurement(1, 2, 1_500), measurement(2, 1, 500), measurement(3, 1, 10)];
+
+        let (report, regressions) = compare(&current, &baseline, 10.0);
+        let lines = report.lines().collect::<Vec<&str>>();
+
+        assert_eq!(regressions, 1);
+        assert!(lines[0].ends_with("no change"));
+        assert!(lines[1].ends_with("REGRESSED"));
+        assert!(lines[2].ends_with("improved"));
+        assert!(lines[3].ends_with("new"));
+    }
+
+    #[test]
+    fn compare_zero_baseline() {
+        let baseline = vec![measurement(1, 1, 0), measurement(1, 2, 0)];
+        let current = vec![measurement(1, 1, 0), measurement(1, 2, 10)];
+
+        let (report, regressions) = compare(&current, &baseline, 10.0);
+        let lines = report.lines().collect::<Vec<&str>>();
+
+        assert_eq!(regressions, 0);
+        assert!(lines[0].ends_with("n/a  not comparable"));
+        assert!(lines[1].ends_with("n/a  not comparable"));
+    }
+}
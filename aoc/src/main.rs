@@ -0,0 +1,380 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use parsing::ParseError;
+use solution::Solution;
+use visualize::Format;
+use visualize::Recorder;
+
+mod bench;
+mod verify;
+
+const SOLUTIONS: [&dyn Solution; 25] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+fn usage() -> ! {
+    eprintln!("usage: aoc run <day> [--part 1|2] [--input <path>]");
+    eprintln!("       aoc run <day> --visualize <directory> [--format png|ppm] [--scale <pixels>] [--input <path>]");
+    eprintln!("       aoc run --all");
+    eprintln!("       aoc verify [--day <day>] [--slow] [--examples]");
+    eprintln!("       aoc bench [--day <day>] [--baseline <path>] [--save-baseline] [--threshold <percent>]");
+    std::process::exit(1);
+}
+
+// Days keep their inputs next to their sources in the workspace.
+fn day_directory(day: usize) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.push(format!("day{:02}", day));
+
+    path
+}
+
+fn default_input(day: usize) -> PathBuf {
+    day_directory(day).join(SOLUTIONS[day - 1].input_file())
+}
+
+fn read_input(path: &PathBuf) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|error| format!("Could not read {}: {}", path.display(), error))
+}
+
+fn solve(solution: &dyn Solution, part: u32, input: &str) -> (Result<String, ParseError>, Duration) {
+    let start = Instant::now();
+    let answer = match part {
+        1 => solution.part1(input),
+        _ => solution.part2(input),
+    };
+
+    (answer, start.elapsed())
+}
+
+fn run_all() {
+    let mut total = Duration::new(0, 0);
+    for day in 1..=SOLUTIONS.len() {
+        let input = match read_input(&default_input(day)) {
+            Ok(input) => input,
+            Err(message) => {
+                println!("day {:02}: {}", day, message);
+                continue;
+            }
+        };
+
+        if let Err(error) = SOLUTIONS[day - 1].check(&input) {
+            println!("day {:02}: bad input: {}", day, error);
+            continue;
+        }
+
+        for part in 1..=2 {
+            let (answer, elapsed) = solve(SOLUTIONS[day - 1], part, &input);
+            total += elapsed;
+            let answer = answer.unwrap_or_else(|error| format!("bad input: {}", error));
+            println!("day {:02} part {} {:>12.3?}  {}", day, part, elapsed, answer.replace("\n", "\n                           "));
+        }
+    }
+
+    println!("total {:.3?}", total);
+}
+
+// Measures the selected days, keeps the results in latest.json and compares
+// them against the baseline if there is one. Exits with failure on
+// regressions, so that it can gate changes.
+fn run_bench(args: &[String]) -> Result<bool, String> {
+    let days = match args.iter().position(|arg| arg == "--day") {
+        Some(index) => match args.get(index + 1).and_then(|day| day.parse::<usize>().ok()) {
+            Some(day) if day >= 1 && day <= SOLUTIONS.len() => vec![day],
+            _ => usage(),
+        },
+        None => (1..=SOLUTIONS.len()).collect(),
+    };
+
+    let baseline_path = match args.iter().position(|arg| arg == "--baseline") {
+        Some(index) => match args.get(index + 1) {
+            Some(path) => PathBuf::from(path),
+            None => usage(),
+        },
+        None => bench::results_directory().join("baseline.json"),
+    };
+
+    let threshold = match args.iter().position(|arg| arg == "--threshold") {
+        Some(index) => match args.get(index + 1).and_then(|threshold| threshold.parse::<f64>().ok()) {
+            Some(threshold) => threshold,
+            None => usage(),
+        },
+        None => 10.0,
+    };
+
+    if cfg!(debug_assertions) {
+        eprintln!("warning: benchmarking a debug build, use --release for meaningful timings");
+    }
+
+    let measurements = bench::run(&days)?;
+    bench::save(&bench::results_directory().join("latest.json"), &measurements)?;
+
+    if args.iter().any(|arg| arg == "--save-baseline") {
+        bench::save(&baseline_path, &measurements)?;
+        println!("saved baseline to {}", baseline_path.display());
+        return Ok(true);
+    }
+
+    if !baseline_path.exists() {
+        println!("no baseline at {}, save one with --save-baseline", baseline_path.display());
+        return Ok(true);
+    }
+
+    let (report, regressions) = bench::compare(&measurements, &bench::load(&baseline_path)?, threshold);
+    println!();
+    print!("{}", report);
+    println!("{} regressed by more than {}%", regressions, threshold);
+
+    Ok(regressions == 0)
+}
+
+// Writes the day's frames into `directory` instead of solving it.
+fn run_visualize(day: usize, input: &str, directory: &Path, args: &[String]) -> Result<(), String> {
+    let format = match args.iter().position(|arg| arg == "--format") {
+        Some(index) => match args.get(index + 1).and_then(|format| Format::from_name(format)) {
+            Some(format) => format,
+            None => usage(),
+        },
+        None => Format::Png,
+    };
+
+    let scale = match args.iter().position(|arg| arg == "--scale") {
+        Some(index) => match args.get(index + 1).and_then(|scale| scale.parse::<usize>().ok()) {
+            Some(scale) if scale >= 1 => scale,
+            _ => usage(),
+        },
+        None => 4,
+    };
+
+    let mut recorder = Recorder::new(directory, format, scale)
+        .map_err(|error| format!("Could not create {}: {}", directory.display(), error))?;
+
+    // the day keeps going after a failed write, only the first error is kept
+    let mut failure = None;
+    let shown = SOLUTIONS[day - 1].visualize(input, &mut |frame| {
+        if failure.is_none() {
+            failure = recorder.record(&frame).err();
+        }
+    }).map_err(|error| error.to_string())?;
+
+    if !shown {
+        return Err(format!("day {:02} has nothing to visualize", day));
+    }
+    if let Some(error) = failure {
+        return Err(format!("Could not write to {}: {}", directory.display(), error));
+    }
+
+    eprintln!("wrote {} frames to {}", recorder.frames(), directory.display());
+    Ok(())
+}
+
+fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    match args.get(1).map(|command| command.as_str()) {
+        Some("run") => {}
+        Some("verify") => {
+            let day = args.iter().position(|arg| arg == "--day").map(|index| {
+                match args.get(index + 1).and_then(|day| day.parse::<usize>().ok()) {
+                    Some(day) => day,
+                    None => usage(),
+                }
+            });
+            let slow = args.iter().any(|arg| arg == "--slow");
+            let examples = args.iter().any(|arg| arg == "--examples");
+
+            match verify::verify(|expectation| {
+                day.is_none_or(|day| expectation.day == day) && (slow || !expectation.slow) && (!examples || expectation.is_example())
+            }) {
+                Ok(report) => {
+                    print!("{}", report.text);
+                    if !report.passed() {
+                        std::process::exit(1);
+                    }
+                }
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some("bench") => {
+            match run_bench(&args) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        _ => usage(),
+    }
+
+    if args.iter().any(|arg| arg == "--all") {
+        run_all();
+        return;
+    }
+
+    let day = match args.get(2).and_then(|day| day.parse::<usize>().ok()) {
+        Some(day) if day >= 1 && day <= SOLUTIONS.len() => day,
+        _ => usage(),
+    };
+
+    let parts = match args.iter().position(|arg| arg == "--part") {
+        Some(index) => match args.get(index + 1).map(|part| part.as_str()) {
+            Some("1") => vec![1],
+            Some("2") => vec![2],
+            _ => usage(),
+        },
+        None => vec![1, 2],
+    };
+
+    let path = match args.iter().position(|arg| arg == "--input") {
+        Some(index) => match args.get(index + 1) {
+            Some(path) => PathBuf::from(path),
+            None => usage(),
+        },
+        None => default_input(day),
+    };
+
+    let input = match read_input(&path) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    };
+
+    // bad input is reported before any part gets to run
+    if let Err(error) = SOLUTIONS[day - 1].check(&input) {
+        eprintln!("{}: {}", path.display(), error);
+        std::process::exit(1);
+    }
+
+    if let Some(index) = args.iter().position(|arg| arg == "--visualize") {
+        let directory = match args.get(index + 1) {
+            Some(directory) => PathBuf::from(directory),
+            None => usage(),
+        };
+
+        if let Err(message) = run_visualize(day, &input, &directory, &args) {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    for part in parts {
+        match solve(SOLUTIONS[day - 1], part, &input).0 {
+            Ok(answer) => println!("{}", answer),
+            Err(error) => {
+                eprintln!("{}: {}", path.display(), error);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use std::panic::AssertUnwindSafe;
+
+    use crate::default_input;
+    use crate::read_input;
+    use crate::verify;
+    use crate::SOLUTIONS;
+
+    fn assert_answers(slow: bool, examples: bool) {
+        let report = verify::verify(|expectation| expectation.slow == slow && expectation.is_example() == examples).unwrap();
+        print!("{}", report.text);
+        assert!(report.passed(), "some answers do not match the expected ones");
+    }
+
+    #[test]
+    fn answers() {
+        assert_answers(false, false);
+    }
+
+    #[test]
+    fn examples() {
+        assert_answers(false, true);
+    }
+
+    #[test]
+    #[ignore]
+    fn slow_answers() {
+        assert_answers(true, false);
+        assert_answers(true, true);
+    }
+
+    // Poor man's fuzzing: every parser gets damaged copies of its day's input
+    // and has to accept or reject them without panicking.
+    #[test]
+    fn parsers_survive_damaged_input() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut random = move |limit: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % std::cmp::max(limit, 1) as u64) as usize
+        };
+        let noise = b"0123456789+-,.#@ \n<>=xyzAZaz)";
+
+        for day in 1..=SOLUTIONS.len() {
+            let input = read_input(&default_input(day)).unwrap();
+            let mut samples = vec![String::new(), "\n \n".to_string()];
+            for _ in 0..200 {
+                let mut bytes = input.clone().into_bytes();
+                for _ in 0..1 + random(3) {
+                    let index = random(bytes.len());
+                    match random(4) {
+                        0 => bytes.truncate(index),
+                        1 if !bytes.is_empty() => { bytes.remove(index); }
+                        2 if !bytes.is_empty() => bytes[index] = noise[random(noise.len())],
+                        _ => {
+                            let copy = bytes[index..].iter().take(random(20)).cloned().collect::<Vec<u8>>();
+                            bytes.splice(index..index, copy);
+                        }
+                    }
+                }
+                samples.push(String::from_utf8_lossy(&bytes).to_string());
+            }
+
+            for sample in samples {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| SOLUTIONS[day - 1].check(&sample)));
+                assert!(result.is_ok(), "day {:02} panicked while parsing {:?}", day, sample);
+            }
+        }
+    }
+}
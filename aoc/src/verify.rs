@@ -0,0 +1,196 @@
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::day_directory;
+use crate::read_input;
+use crate::solve;
+use crate::SOLUTIONS;
+
+pub struct Expectation {
+    pub day: usize,
+    pub part: u32,
+    pub file: String,
+    pub answer: String,
+    // takes too long to check on every test run
+    pub slow: bool,
+}
+
+pub struct Outcome {
+    pub expectation: Expectation,
+    pub actual: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+impl Expectation {
+    // Examples live in the day's examples folder, next to the list of their
+    // answers.
+    pub fn is_example(&self) -> bool {
+        self.file.starts_with("examples/")
+    }
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Ok(&self.expectation.answer)
+    }
+}
+
+pub struct Report {
+    pub outcomes: Vec<Outcome>,
+    pub text: String,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.passed())
+    }
+}
+
+fn answers_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("answers.txt")
+}
+
+fn examples_directory(day: usize) -> PathBuf {
+    day_directory(day).join("examples")
+}
+
+// Lists of examples belong to a single day and have no day column.
+fn parse_lines(text: &str, name: &str, day: Option<usize>) -> Result<Vec<Expectation>, String> {
+    let format = if day.is_some() { "part file answer [slow]" } else { "day part file answer [slow]" };
+    let columns = format.split_whitespace().count() - 1;
+
+    let mut expectations = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        let error = |message: &str| format!("{}:{}: {}: {}", name, index + 1, message, line);
+        if fields.len() != columns && !(fields.len() == columns + 1 && fields[columns] == "slow") {
+            return Err(error(&format!("expected `{}`", format)));
+        }
+
+        let (day, fields) = match day {
+            Some(day) => (day, &fields[..]),
+            None => match fields[0].parse::<usize>() {
+                Ok(day) if day >= 1 && day <= SOLUTIONS.len() => (day, &fields[1..]),
+                _ => return Err(error("invalid day")),
+            },
+        };
+        let part = match fields[0] {
+            "1" => 1,
+            "2" => 2,
+            _ => return Err(error("invalid part")),
+        };
+
+        expectations.push(Expectation {
+            day,
+            part,
+            file: fields[1].to_string(),
+            answer: fields[2].to_string(),
+            slow: fields.len() == 4,
+        });
+    }
+
+    Ok(expectations)
+}
+
+pub fn parse(text: &str) -> Result<Vec<Expectation>, String> {
+    parse_lines(text, "answers.txt", None)
+}
+
+pub fn parse_examples(day: usize, text: &str) -> Result<Vec<Expectation>, String> {
+    let name = format!("day{:02}/examples/answers.txt", day);
+    let mut expectations = parse_lines(text, &name, Some(day))?;
+    for expectation in &mut expectations {
+        expectation.file = format!("examples/{}", expectation.file);
+    }
+
+    Ok(expectations)
+}
+
+// The puzzle answers for the real inputs, followed by the examples of every
+// day that has an examples folder.
+pub fn load() -> Result<Vec<Expectation>, String> {
+    let mut expectations = parse(&read_input(&answers_path())?)?;
+    for day in 1..=SOLUTIONS.len() {
+        let path = examples_directory(day).join("answers.txt");
+        if path.exists() {
+            expectations.extend(parse_examples(day, &read_input(&path)?)?);
+        }
+    }
+
+    Ok(expectations)
+}
+
+// A solver that panics fails its own entry instead of the whole run.
+pub fn check(expectation: Expectation) -> Outcome {
+    let path = day_directory(expectation.day).join(&expectation.file);
+    let (actual, elapsed) = match read_input(&path) {
+        Ok(input) => {
+            let solution = SOLUTIONS[expectation.day - 1];
+            match panic::catch_unwind(AssertUnwindSafe(|| solve(solution, expectation.part, &input))) {
+                Ok((Ok(answer), elapsed)) => (Ok(answer), elapsed),
+                Ok((Err(error), elapsed)) => (Err(format!("bad input: {}", error)), elapsed),
+                Err(_) => (Err(String::from("solver panicked")), Duration::new(0, 0)),
+            }
+        }
+        Err(message) => (Err(message), Duration::new(0, 0)),
+    };
+
+    Outcome { expectation, actual, elapsed }
+}
+
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<&str>>();
+    let actual = actual.lines().collect::<Vec<&str>>();
+
+    let mut text = String::new();
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => text.push_str(&format!("      {}\n", expected)),
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    text.push_str(&format!("    - {}\n", expected));
+                }
+                if let Some(actual) = actual {
+                    text.push_str(&format!("    + {}\n", actual));
+                }
+            }
+        }
+    }
+
+    text
+}
+
+pub fn report(outcomes: Vec<Outcome>) -> Report {
+    let mut text = String::new();
+    for outcome in &outcomes {
+        let expectation = &outcome.expectation;
+        let status = if outcome.passed() { "ok" } else { "FAILED" };
+        text.push_str(&format!(
+            "day {:02} part {} {:<24} {:>12.3?}  {}\n",
+            expectation.day, expectation.part, expectation.file, outcome.elapsed, status
+        ));
+
+        match &outcome.actual {
+            Ok(actual) if !outcome.passed() => text.push_str(&diff(&expectation.answer, actual)),
+            Err(message) => text.push_str(&format!("    {}\n", message)),
+            _ => {}
+        }
+    }
+
+    let passed = outcomes.iter().filter(|outcome| outcome.passed()).count();
+    text.push_str(&format!("{} passed, {} failed\n", passed, outcomes.len() - passed));
+
+    Report { outcomes, text }
+}
+
+pub fn verify<F: Fn(&Expectation) -> bool>(filter: F) -> Result<Report, String> {
+    let expectations = load()?;
+    Ok(report(expectations.into_iter().filter(|expectation| filter(expectation)).map(check).collect()))
+}
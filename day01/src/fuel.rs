@@ -0,0 +1,109 @@
+// Masses are unsigned, so that fuel never goes negative: anything that would
+// need less than nothing needs no fuel at all. A module's fuel, even with the
+// fuel for the fuel, is at most half its mass and fits the mass's type, and
+// totals are summed in u128, which no realistic list of modules can overflow.
+
+// The fuel for a mass on its own, not counting the fuel's own mass.
+pub fn module_fuel(mass: u64) -> u64 {
+    (mass / 3).saturating_sub(2)
+}
+
+// The fuel for a mass, then the fuel for that fuel, and so on while it takes
+// any.
+pub struct FuelTerms {
+    mass: u64,
+}
+
+impl Iterator for FuelTerms {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        match module_fuel(self.mass) {
+            0 => None,
+            fuel => {
+                self.mass = fuel;
+                Some(fuel)
+            }
+        }
+    }
+}
+
+pub fn fuel_terms(mass: u64) -> FuelTerms {
+    FuelTerms { mass }
+}
+
+// The fuel for a mass including the fuel for the fuel.
+pub fn recursive_fuel(mass: u64) -> u64 {
+    fuel_terms(mass).sum()
+}
+
+pub fn total_fuel(masses: &[u64]) -> u128 {
+    masses.iter().map(|&mass| module_fuel(mass) as u128).sum()
+}
+
+pub fn total_recursive_fuel(masses: &[u64]) -> u128 {
+    masses.iter().map(|&mass| recursive_fuel(mass) as u128).sum()
+}
+
+// What one module needs: the fuel for its mass and the extra fuel carrying
+// that fuel takes.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Breakdown {
+    pub mass: u64,
+    pub base: u64,
+    pub extra: u64,
+}
+
+impl Breakdown {
+    pub fn of(mass: u64) -> Breakdown {
+        let base = module_fuel(mass);
+
+        Breakdown { mass, base, extra: recursive_fuel(base) }
+    }
+}
+
+// One line per module followed by the column totals.
+pub fn report(masses: &[u64]) -> String {
+    let mut text = format!("{:>20} {:>20} {:>20}\n", "mass", "base fuel", "extra fuel");
+    let (mut mass, mut base, mut extra) = (0_u128, 0_u128, 0_u128);
+    for breakdown in masses.iter().map(|&mass| Breakdown::of(mass)) {
+        text.push_str(&format!("{:>20} {:>20} {:>20}\n", breakdown.mass, breakdown.base, breakdown.extra));
+        mass += breakdown.mass as u128;
+        base += breakdown.base as u128;
+        extra += breakdown.extra as u128;
+    }
+    text.push_str(&format!("{}\n", "-".repeat(62)));
+    text.push_str(&format!("{:>20} {:>20} {:>20}\n", mass, base, extra));
+    text.push_str(&format!("total fuel {}\n", base + extra));
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(module_fuel(12), 2);
+        assert_eq!(module_fuel(14), 2);
+        assert_eq!(module_fuel(1969), 654);
+        assert_eq!(module_fuel(100756), 33583);
+
+        assert_eq!(recursive_fuel(14), 2);
+        assert_eq!(fuel_terms(1969).collect::<Vec<u64>>(), vec![654, 216, 70, 21, 5]);
+        assert_eq!(recursive_fuel(100756), 50346);
+        assert_eq!(Breakdown::of(1969), Breakdown { mass: 1969, base: 654, extra: 312 });
+    }
+
+    #[test]
+    fn small_and_large_masses() {
+        assert_eq!(module_fuel(0), 0);
+        assert_eq!(module_fuel(5), 0);
+        assert_eq!(fuel_terms(8).count(), 0);
+
+        assert_eq!(module_fuel(u64::MAX), u64::MAX / 3 - 2);
+        assert!(recursive_fuel(u64::MAX) < u64::MAX / 2);
+        assert_eq!(total_fuel(&[u64::MAX, u64::MAX]), 2 * (u64::MAX / 3 - 2) as u128);
+    }
+}
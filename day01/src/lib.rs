@@ -0,0 +1,30 @@
+pub mod fuel;
+
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day01;
+
+// One module mass per line.
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parsing::lines(input).map(|mut line| {
+        let mass = line.number()?;
+        line.end()?;
+
+        Ok(mass)
+    }).collect()
+}
+
+impl Solution for Day01 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(fuel::total_fuel(&parse(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(fuel::total_recursive_fuel(&parse(input)?).to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_masses| ())
+    }
+}
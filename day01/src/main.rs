@@ -1,21 +1,17 @@
-use std::io::BufRead;
+use std::io::Read;
+
+use day01::Day01;
+use solution::or_exit;
+use solution::Solution;
 
 fn main() {
-    let mut total_fuel = 0;
-    let mut total_additional_fuel = 0;
-    for line in std::io::stdin().lock().lines() {
-        let mass: i32 = line.unwrap().parse().unwrap();
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
 
-        let fuel = mass / 3 - 2;
-        total_fuel += fuel;
+    println!("{}", or_exit(Day01.part1(&input)));
+    println!("{}", or_exit(Day01.part2(&input)));
 
-        let mut additional_fuel = fuel / 3 - 2;
-        while additional_fuel > 0 {
-            total_additional_fuel += additional_fuel;
-            additional_fuel = additional_fuel / 3 - 2;
-        }
+    if std::env::args().any(|arg| arg == "--report") {
+        print!("{}", day01::fuel::report(&or_exit(day01::parse(&input))));
     }
-
-    println!("{}", total_fuel);
-    println!("{}", total_fuel + total_additional_fuel);
 }
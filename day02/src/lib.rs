@@ -0,0 +1,78 @@
+pub mod symbolic;
+
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day02;
+
+fn run(mut program: Vec::<usize>) -> usize {
+    let mut pc = 0;
+    while program[pc] != 99 {
+        let source1 = program[pc + 1];
+        let source2 = program[pc + 2];
+        let destination = program[pc + 3];
+
+        program[destination] = match program[pc] {
+            1 => program[source1] + program[source2],
+            2 => program[source1] * program[source2],
+            _ => panic!("Illegal operation."),
+        };
+
+        pc += 4;
+    }
+
+    // dbg!(program);
+
+    return program[0];
+}
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+const TARGET: usize = 19690720;
+
+// The noun and verb that make the program produce the target, together with
+// how they were found.
+pub fn noun_and_verb(program: &[usize]) -> Option<((usize, usize), symbolic::Method)> {
+    symbolic::solve(program, TARGET, run)
+}
+
+pub fn answer(solution: &Option<((usize, usize), symbolic::Method)>) -> String {
+    match solution {
+        Some(((noun, verb), _)) => (100 * noun + verb).to_string(),
+        None => format!("No noun and verb produce {}.", TARGET),
+    }
+}
+
+impl Solution for Day02 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let mut program = parse(input)?;
+        program[1] = 12;
+        program[2] = 2;
+
+        Ok(run(program).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(answer(&noun_and_verb(&parse(input)?)))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(run(parse(include_str!("../examples/program.txt")).unwrap()), 3500);
+        assert_eq!(run(vec![1, 0, 0, 0, 99]), 2);
+        assert_eq!(run(vec![2, 3, 0, 3, 99]), 2);
+        assert_eq!(run(vec![2, 4, 4, 5, 99, 0]), 2);
+        assert_eq!(run(vec![1, 1, 1, 4, 99, 5, 6, 0, 99]), 30);
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+// A polynomial in noun and verb, mapping (noun degree, verb degree) to the
+// coefficient of that term.
+#[derive(Clone, Debug, PartialEq)]
+struct Polynomial {
+    terms: BTreeMap<(u32, u32), i64>,
+}
+
+impl Polynomial {
+    fn constant(value: i64) -> Polynomial {
+        Polynomial::term((0, 0), value)
+    }
+
+    fn term(degrees: (u32, u32), coefficient: i64) -> Polynomial {
+        let mut terms = BTreeMap::new();
+        if coefficient != 0 {
+            terms.insert(degrees, coefficient);
+        }
+
+        Polynomial { terms }
+    }
+
+    fn coefficient(&self, degrees: (u32, u32)) -> i64 {
+        *self.terms.get(&degrees).unwrap_or(&0)
+    }
+
+    fn as_constant(&self) -> Option<i64> {
+        if self.terms.keys().all(|degrees| *degrees == (0, 0)) {
+            Some(self.coefficient((0, 0)))
+        } else {
+            None
+        }
+    }
+
+    // None if a coefficient overflows.
+    fn add(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut terms = self.terms.clone();
+        for (degrees, coefficient) in &other.terms {
+            let sum = terms.entry(*degrees).or_insert(0);
+            *sum = sum.checked_add(*coefficient)?;
+        }
+        terms.retain(|_, coefficient| *coefficient != 0);
+
+        Some(Polynomial { terms })
+    }
+
+    // None if a coefficient overflows.
+    fn mul(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut terms: BTreeMap<(u32, u32), i64> = BTreeMap::new();
+        for (left, a) in &self.terms {
+            for (right, b) in &other.terms {
+                let sum = terms.entry((left.0 + right.0, left.1 + right.1)).or_insert(0);
+                *sum = a.checked_mul(*b).and_then(|product| sum.checked_add(product))?;
+            }
+        }
+        terms.retain(|_, coefficient: &mut i64| *coefficient != 0);
+
+        Some(Polynomial { terms })
+    }
+
+    fn is_affine(&self) -> bool {
+        self.terms.keys().all(|degrees| degrees.0 + degrees.1 <= 1)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Method {
+    Symbolic,
+    BruteForce,
+}
+
+// Runs the program with noun and verb left unknown. A value loaded through a
+// symbolic address is unknown as well, which is harmless unless it ends up
+// as an opcode, a write address or the result. Gives up on coefficients that
+// do not fit an i64.
+fn evaluate(program: &[usize]) -> Option<Polynomial> {
+    let mut memory = program.iter().map(|value| Some(Polynomial::constant(*value as i64))).collect::<Vec<Option<Polynomial>>>();
+    memory[1] = Some(Polynomial::term((1, 0), 1));
+    memory[2] = Some(Polynomial::term((0, 1), 1));
+
+    let mut pc = 0;
+    loop {
+        let opcode = memory.get(pc)?.as_ref()?.as_constant()?;
+        if opcode == 99 {
+            break;
+        }
+
+        let address = |offset: usize| {
+            let address = memory.get(pc + offset)?.as_ref()?.as_constant()?;
+            if address >= 0 && (address as usize) < memory.len() {
+                Some(address as usize)
+            } else {
+                None
+            }
+        };
+        let operand1 = address(1).and_then(|source| memory[source].clone());
+        let operand2 = address(2).and_then(|source| memory[source].clone());
+        let destination = address(3)?;
+
+        memory[destination] = match (opcode, operand1, operand2) {
+            (1, Some(operand1), Some(operand2)) => Some(operand1.add(&operand2)?),
+            (2, Some(operand1), Some(operand2)) => Some(operand1.mul(&operand2)?),
+            (1, _, _) | (2, _, _) => None,
+            _ => return None,
+        };
+
+        pc += 4;
+    }
+
+    memory[0].clone()
+}
+
+// Solves target = offset + noun_factor * noun + verb_factor * verb for the
+// first noun that has a verb. The outer None means the arithmetic overflowed.
+fn solve_affine(result: &Polynomial, target: usize) -> Option<Option<(usize, usize)>> {
+    let offset = result.coefficient((0, 0));
+    let noun_factor = result.coefficient((1, 0));
+    let verb_factor = result.coefficient((0, 1));
+
+    for noun in 0..100 {
+        let rest = (target as i64).checked_sub(offset)?.checked_sub(noun_factor.checked_mul(noun)?)?;
+        let verb = match verb_factor {
+            0 if rest == 0 => 0,
+            0 => continue,
+            _ if rest % verb_factor != 0 => continue,
+            _ => rest / verb_factor,
+        };
+
+        if (0..100).contains(&verb) {
+            return Some(Some((noun as usize, verb as usize)));
+        }
+    }
+
+    Some(None)
+}
+
+pub fn solve<F>(program: &[usize], target: usize, run: F) -> Option<((usize, usize), Method)> where F: Fn(Vec<usize>) -> usize {
+    if let Some(result) = evaluate(program) {
+        if result.is_affine() {
+            if let Some(solution) = solve_affine(&result, target) {
+                return solution.map(|solution| (solution, Method::Symbolic));
+            }
+        }
+    }
+
+    let mut program = program.to_vec();
+    for noun in 0..100 {
+        for verb in 0..100 {
+            program[1] = noun;
+            program[2] = verb;
+            if run(program.clone()) == target {
+                return Some(((noun, verb), Method::BruteForce));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(program: &[usize], target: usize) -> Option<(usize, usize)> {
+        let mut program = program.to_vec();
+        for noun in 0..100 {
+            for verb in 0..100 {
+                program[1] = noun;
+                program[2] = verb;
+                if crate::run(program.clone()) == target {
+                    return Some((noun, verb));
+                }
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let program = crate::parse(include_str!("../input.txt")).unwrap();
+        for (noun, verb) in [(0, 0), (12, 2), (50, 99), (99, 0), (99, 99)] {
+            let mut inputs = program.clone();
+            inputs[1] = noun;
+            inputs[2] = verb;
+            let target = crate::run(inputs);
+
+            let solution = solve(&program, target, crate::run);
+            assert_eq!(solution, brute_force(&program, target).map(|solution| (solution, Method::Symbolic)));
+        }
+
+        assert_eq!(solve(&program, 0, crate::run), None);
+    }
+
+    #[test]
+    fn non_linear_fallback() {
+        // the first instruction always uses noun and verb as addresses, so
+        // the second one computes noun * verb
+        let mut program = vec![1, 0, 0, 3, 2, 1, 2, 0, 99];
+        program.resize(100, 0);
+        assert_eq!(solve(&program, 12, crate::run), Some(((1, 12), Method::BruteForce)));
+        assert_eq!(solve(&program, 9999, crate::run), None);
+
+        // noun * 2^80 overflows the coefficients
+        let program = vec![1, 0, 0, 3, 2, 1, 13, 1, 2, 1, 13, 1, 99, 1 << 40];
+        assert_eq!(evaluate(&program), None);
+        let run = |program: Vec<usize>| if (program[1], program[2]) == (3, 4) { 7 } else { 0 };
+        assert_eq!(solve(&program, 7, run), Some(((3, 4), Method::BruteForce)));
+    }
+}
@@ -0,0 +1,195 @@
+use geometry::Bounds;
+use geometry::Direction;
+use geometry::Position;
+use parsing::ParseError;
+use parsing::Scanner;
+use solution::Solution;
+
+pub struct Day03;
+
+#[derive(Debug)]
+struct Segment {
+    direction: Direction,
+    length: i64,
+}
+
+fn parse_wire(mut line: Scanner) -> Result<Vec<Segment>, ParseError> {
+    let mut wire = Vec::new();
+    loop {
+        let direction = match line.one_of("URDL")? {
+            'U' => Direction::Up,
+            'R' => Direction::Right,
+            'D' => Direction::Down,
+            _ => Direction::Left,
+        };
+
+        let length = line.number::<u32>()? as i64;
+
+        wire.push(Segment { direction, length });
+        if !line.accept(",") {
+            break;
+        }
+    }
+    line.end()?;
+
+    return Ok(wire);
+}
+
+// One wire per line, at least two of them.
+fn parse(input: &str) -> Result<Vec<Vec<Segment>>, ParseError> {
+    let wires = parsing::lines(input).map(parse_wire).collect::<Result<Vec<_>, ParseError>>()?;
+    if wires.len() < 2 {
+        return Err(ParseError::at_end(input, "expected at least two wires"));
+    }
+
+    Ok(wires)
+}
+
+// A straight piece of wire between two corners, together with the number of
+// steps the signal takes to reach its start.
+#[derive(Debug)]
+struct Line {
+    start: Position,
+    bounds: Bounds,
+    delay: i64,
+}
+
+fn trace(wire: &[Segment]) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut start = Position { x: 0, y: 0 };
+    let mut delay = 0;
+    for segment in wire {
+        let unit = Position { x: 0, y: 0 }.step(&segment.direction);
+        let end = Position { x: start.x + unit.x * segment.length, y: start.y + unit.y * segment.length };
+
+        let bounds = Bounds::of(&[start.clone(), end.clone()]).unwrap();
+        lines.push(Line { start, bounds, delay });
+        start = end;
+        delay += segment.length;
+    }
+
+    lines
+}
+
+// A position shared by two wires and the signal delay there, the sum of the
+// steps both wires take to get to it.
+#[derive(PartialEq, Debug)]
+struct Crossing {
+    position: Position,
+    delay: i64,
+}
+
+// The shared positions that can be closest to the port or have the lowest
+// delay. Along a stretch both are smallest at one of its ends, or, for the
+// distance, where it passes nearest to the port. The port itself does not
+// count, so its neighbours stand in for it.
+fn candidates(shared: &Bounds) -> Vec<Position> {
+    let port = Position { x: 0, y: 0 };
+    let nearest = Position { x: port.x.clamp(shared.min.x, shared.max.x), y: port.y.clamp(shared.min.y, shared.max.y) };
+
+    let mut candidates = vec![shared.min.clone(), shared.max.clone()];
+    if nearest == port {
+        candidates.extend(port.neighbours().into_iter().filter(|neighbour| shared.contains(neighbour)));
+    } else {
+        candidates.push(nearest);
+    }
+
+    candidates.retain(|position| *position != port);
+    candidates.dedup();
+    candidates
+}
+
+// Lines are axis-aligned, so two of them share exactly the positions in the
+// intersection of their bounds: a single point when they are perpendicular, a
+// stretch when they overlap lengthwise.
+fn crossings(wire1: &[Line], wire2: &[Line]) -> Vec<Crossing> {
+    let mut crossings = Vec::new();
+    for line1 in wire1 {
+        for line2 in wire2 {
+            let shared = match line1.bounds.intersection(&line2.bounds) {
+                Some(shared) => shared,
+                None => continue,
+            };
+
+            for position in candidates(&shared) {
+                let delay1 = line1.delay + line1.start.manhattan_distance(&position);
+                let delay2 = line2.delay + line2.start.manhattan_distance(&position);
+                crossings.push(Crossing { position, delay: delay1 + delay2 });
+            }
+        }
+    }
+
+    crossings
+}
+
+// Where any two of the wires cross. A wire that passes a crossing more than
+// once shows up there once per pass; the lowest delay is the one that counts.
+fn all_crossings(wires: &[Vec<Segment>]) -> Vec<Crossing> {
+    let traced = wires.iter().map(|wire| trace(wire)).collect::<Vec<Vec<Line>>>();
+
+    let mut all = Vec::new();
+    for (index, wire1) in traced.iter().enumerate() {
+        for wire2 in &traced[index + 1..] {
+            all.extend(crossings(wire1, wire2));
+        }
+    }
+
+    all
+}
+
+fn answer(value: Option<i64>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "The wires do not cross.".to_string(),
+    }
+}
+
+// The distance from the central port to the closest crossing.
+fn closest_distance(wires: &[Vec<Segment>]) -> Option<i64> {
+    let port = Position { x: 0, y: 0 };
+    all_crossings(wires).iter().map(|crossing| crossing.position.manhattan_distance(&port)).min()
+}
+
+fn shortest_delay(wires: &[Vec<Segment>]) -> Option<i64> {
+    all_crossings(wires).iter().map(|crossing| crossing.delay).min()
+}
+
+impl Solution for Day03 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(answer(closest_distance(&parse(input)?)))
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(answer(shortest_delay(&parse(input)?)))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_wires| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn more_than_two_wires() {
+        let wires = parse("R8,U5,L5,D3\nU7,R6,D4,L4\nL2,U6,R9\n").unwrap();
+        assert_eq!(closest_distance(&wires), Some(6));
+        assert_eq!(shortest_delay(&wires), Some(16));
+    }
+
+    #[test]
+    fn overlapping_and_missing_wires() {
+        let wires = parse("R10\nU1,R3,D1,R5\nL1\n").unwrap();
+        assert_eq!(closest_distance(&wires), Some(3));
+        assert_eq!(shortest_delay(&wires), Some(8));
+
+        assert_eq!(closest_distance(&parse("R5\nL5\n").unwrap()), None);
+        assert!(parse("R5\n").is_err());
+
+        let wires = parse("L3000000000,U5\nR1,L3000000000\n").unwrap();
+        assert_eq!(closest_distance(&wires), Some(1));
+        assert_eq!(shortest_delay(&wires), Some(4));
+    }
+}
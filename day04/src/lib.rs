@@ -0,0 +1,77 @@
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day04;
+
+fn parse(input: &str) -> Result<(u32, u32), ParseError> {
+    let mut line = parsing::first_line(input)?;
+    let start = line.number()?;
+    line.expect("-")?;
+    let end = line.number()?;
+    line.end()?;
+
+    Ok((start, end))
+}
+
+fn count_valid((start, end): (u32, u32)) -> (u32, u32) {
+    let mut num_valid1 = 0;
+    let mut num_valid2 = 0;
+    for candidate in start..=end {
+        let mut last_digit = candidate % 10;
+        let mut other_digits = candidate / 10;
+
+        let mut double = false;
+        let mut run_length = 1;
+        let mut true_double = false;
+        let mut decreasing = false;
+        while other_digits > 0 {
+            let new_last_digit = other_digits % 10;
+
+            if new_last_digit == last_digit {
+                double = true;
+                run_length += 1;
+            } else {
+                if run_length == 2 {
+                    true_double = true;
+                }
+                run_length = 1;
+            }
+
+            if new_last_digit > last_digit {
+                decreasing = true;
+                break;
+            }
+
+            last_digit = new_last_digit;
+            other_digits = other_digits / 10;
+        }
+
+        if run_length == 2 {
+            true_double = true;
+        }
+
+        if double && !decreasing {
+            num_valid1 += 1;
+
+            if true_double {
+                num_valid2 += 1;
+            }
+        }
+    }
+
+    (num_valid1, num_valid2)
+}
+
+impl Solution for Day04 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_valid(parse(input)?).0.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_valid(parse(input)?).1.to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_range| ())
+    }
+}
@@ -0,0 +1,114 @@
+use intcode::run;
+use intcode::Context;
+
+// Anything that can run the TEST program: it has to hand out the outputs one
+// at a time together with the address of the instruction that produced them.
+pub trait Machine {
+    fn next_output(&mut self) -> Option<(usize, i64)>;
+
+    // Whether the last call stopped at an input instruction with nothing left
+    // to read instead of at the end of the program.
+    fn starved(&self) -> bool;
+}
+
+impl Machine for Context {
+    fn next_output(&mut self) -> Option<(usize, i64)> {
+        let output = run(self)?;
+
+        // run() stops right behind the two-cell output instruction
+        Some((self.pc - 2, output))
+    }
+
+    fn starved(&self) -> bool {
+        self.blocked
+    }
+}
+
+pub struct Report {
+    pub checks: Vec<(usize, i64)>,
+    pub failures: Vec<(usize, i64)>,
+    pub code: Option<i64>,
+    pub starved: bool,
+}
+
+// Every output but the last one is a check that passed if it is zero. The
+// last output is the diagnostic code.
+pub fn diagnose<M: Machine>(machine: &mut M) -> Report {
+    let mut outputs = Vec::new();
+    while let Some(output) = machine.next_output() {
+        outputs.push(output);
+    }
+
+    let code = outputs.pop().map(|(_, value)| value);
+    let failures = outputs.iter().filter(|(_, value)| *value != 0).cloned().collect();
+
+    Report {
+        checks: outputs,
+        failures,
+        code,
+        starved: machine.starved(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_input() {
+        let mut context = Context::new(&[3, 0, 4, 0, 99]);
+        let report = diagnose(&mut context);
+
+        assert!(report.starved);
+        assert_eq!(report.code, None);
+
+        context.inputs.push_back(7);
+        let report = diagnose(&mut context);
+        assert!(!report.starved);
+        assert_eq!((report.checks.len(), report.code), (0, Some(7)));
+    }
+
+    // Hands out prepared outputs, then stops as if halted or starved.
+    struct Replay {
+        outputs: Vec<(usize, i64)>,
+        starved: bool,
+    }
+
+    impl Machine for Replay {
+        fn next_output(&mut self) -> Option<(usize, i64)> {
+            if self.outputs.is_empty() {
+                None
+            } else {
+                Some(self.outputs.remove(0))
+            }
+        }
+
+        fn starved(&self) -> bool {
+            self.starved
+        }
+    }
+
+    #[test]
+    fn checks_and_code() {
+        let mut machine = Replay { outputs: vec![(10, 0), (20, 3), (30, 0), (40, -1), (50, 1234)], starved: false };
+        let report = diagnose(&mut machine);
+
+        assert_eq!(report.checks.len(), 4);
+        assert_eq!(report.failures, vec![(20, 3), (40, -1)]);
+        assert_eq!(report.code, Some(1234));
+        assert!(!report.starved);
+
+        let report = diagnose(&mut Replay { outputs: vec![], starved: false });
+        assert!(report.checks.is_empty());
+        assert_eq!(report.code, None);
+    }
+
+    #[test]
+    fn output_addresses() {
+        let mut context = Context::new(&[104, 0, 1101, 2, 3, 11, 4, 11, 99, 0, 0, 0]);
+        let report = diagnose(&mut context);
+
+        assert_eq!(report.checks, vec![(0, 0)]);
+        assert_eq!(report.code, Some(5));
+    }
+}
@@ -0,0 +1,90 @@
+pub mod diagnostics;
+
+use intcode::Context;
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day05;
+
+fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+// The program's outputs for a single system ID, the last one being the
+// diagnostic code.
+fn diagnostic_code(input: &str, system: i64) -> Result<String, ParseError> {
+    let mut context = Context::new(&parse(input)?);
+    context.inputs.push_back(system);
+
+    let report = diagnostics::diagnose(&mut context);
+    if report.starved {
+        return Ok("the program needs more input".to_string());
+    }
+
+    match report.code {
+        Some(code) => Ok(code.to_string()),
+        None => Ok("no diagnostic code".to_string()),
+    }
+}
+
+impl Solution for Day05 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        diagnostic_code(input, 1)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        diagnostic_code(input, 5)
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+
+    fn input_file(&self) -> &'static str {
+        "input1.txt"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::run;
+
+    // Program on the first line, inputs on the following ones.
+    fn outputs(source: &str) -> Vec<i64> {
+        let mut lines = source.lines();
+        let program = lines.next().unwrap().trim().split(",").map(|token| token.parse::<i64>().unwrap()).collect::<Vec<i64>>();
+
+        let mut context = Context::new(&program);
+        context.inputs = lines.filter(|line| !line.trim().is_empty()).map(|line| line.trim().parse::<i64>().unwrap()).collect();
+
+        let mut outputs = Vec::new();
+        while let Some(output) = run(&mut context) {
+            outputs.push(output);
+        }
+
+        outputs
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(outputs(include_str!("../input1.txt")), vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 4601506]);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(outputs(include_str!("../input2.txt")), vec![5525561]);
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(outputs(include_str!("../examples/multiply.txt")), vec![]);
+        assert_eq!(outputs(include_str!("../examples/equal-to-8-position.txt")), vec![1]);
+        assert_eq!(outputs(include_str!("../examples/less-than-8-position.txt")), vec![1]);
+        assert_eq!(outputs(include_str!("../examples/equal-to-8-immediate.txt")), vec![0]);
+        assert_eq!(outputs(include_str!("../examples/less-than-8-immediate.txt")), vec![1]);
+        assert_eq!(outputs(include_str!("../examples/jump-position.txt")), vec![1]);
+        assert_eq!(outputs(include_str!("../examples/jump-immediate.txt")), vec![1]);
+        assert_eq!(outputs(include_str!("../examples/compare-to-8.txt")), vec![1001]);
+    }
+}
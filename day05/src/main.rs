@@ -1,112 +1,52 @@
-fn fetch(program: &Vec::<i32>, pc: usize, offset: u32) -> i32 {
-    let mode = program[pc] / (10i32.pow(offset + 1)) % 10;
-    // dbg!(mode);
+use std::collections::VecDeque;
 
-    match mode {
-        0 => {
-            let address = program[pc + offset as usize] as usize;
-            return program[address];
-        }
-        1 => {
-            return program[pc + offset as usize];
+use intcode::run;
+use intcode::Context;
+use parsing::Scanner;
+use solution::or_exit;
+
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).unwrap();
+    let program = or_exit(Scanner::new(input.trim_end(), 1).numbers(","));
+
+    // the program's inputs follow, one per line
+    let mut inputs = VecDeque::new();
+    for (index, line) in std::io::stdin().lines().enumerate() {
+        let line = line.unwrap();
+        if !line.trim().is_empty() {
+            let mut scanner = Scanner::new(line.trim(), index + 2);
+            let value = or_exit(scanner.number::<i64>());
+            or_exit(scanner.end());
+            inputs.push_back(value);
         }
-        _ => panic!("Illegal mode flag."),
     }
-}
-
-fn run(mut program: Vec::<i32>) {
-    let mut pc = 0;
-    while program[pc] != 99 {
-        // println!("{}: {}", pc, program[pc]);
-        match program[pc] % 100 {
-            1 => {
-                let operand1 = fetch(&program, pc, 1);
-                let operand2 = fetch(&program, pc, 2);
-                let destination = program[pc + 3] as usize;
-
-                program[destination] = operand1 + operand2;
-                pc += 4;
-            }
-            2 => {
-                let operand1 = fetch(&program, pc, 1);
-                let operand2 = fetch(&program, pc, 2);
-                let destination = program[pc + 3] as usize;
-
-                program[destination] = operand1 * operand2;
-                pc += 4;
-            }
-            3 => {
-                let destination = program[pc + 1] as usize;
-
-                let mut input = String::new();
-                std::io::stdin().read_line(&mut input).unwrap();
-                input = input.trim_end().to_string();
-                program[destination] = input.parse::<i32>().unwrap();
-                pc += 2;
-            }
-            4 => {
-                let operand = fetch(&program, pc, 1);
-
-                println!("{}", operand);
-                pc += 2;
-            }
-            5 => {
-                let operand1 = fetch(&program, pc, 1);
-                let operand2 = fetch(&program, pc, 2);
 
-                if operand1 != 0 {
-                    pc = operand2 as usize;
-                } else {
-                    pc += 3;
-                }
-            }
-            6 => {
-                let operand1 = fetch(&program, pc, 1);
-                let operand2 = fetch(&program, pc, 2);
+    let mut context = Context::new(&program);
+    context.inputs = inputs;
 
-                if operand1 == 0 {
-                    pc = operand2 as usize;
-                } else {
-                    pc += 3;
-                }
-            }
-            7 => {
-                let operand1 = fetch(&program, pc, 1);
-                let operand2 = fetch(&program, pc, 2);
-                let destination = program[pc + 3] as usize;
-
-                if operand1 < operand2 {
-                    program[destination] = 1;
-                } else {
-                    program[destination] = 0;
-                }
-                pc += 4;
-            }
-            8 => {
-                let operand1 = fetch(&program, pc, 1);
-                let operand2 = fetch(&program, pc, 2);
-                let destination = program[pc + 3] as usize;
-
-                if operand1 == operand2 {
-                    program[destination] = 1;
-                } else {
-                    program[destination] = 0;
-                }
-                pc += 4;
-            }
-            _ => panic!("Illegal operation."),
-        };
+    if std::env::args().any(|arg| arg == "--diagnose") {
+        let report = day05::diagnostics::diagnose(&mut context);
+        for (pc, value) in &report.failures {
+            println!("check at {} failed with {}", pc, value);
+        }
+        println!("{} of {} checks passed", report.checks.len() - report.failures.len(), report.checks.len());
+        match report.code {
+            Some(code) => println!("{}", code),
+            None => println!("no diagnostic code"),
+        }
+        if report.starved {
+            eprintln!("The program needs more input than was given.");
+            std::process::exit(1);
+        }
+        return;
     }
-}
 
-fn main() {
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input = input.trim_end().to_string();
-    let mut program = Vec::new();
-    for token in input.split(",") {
-        program.push(token.parse::<i32>().expect(token));
+    while let Some(output) = run(&mut context) {
+        println!("{}", output);
+    }
+    if context.blocked {
+        eprintln!("The program needs more input than was given.");
+        std::process::exit(1);
     }
-
-    run(program);
 }
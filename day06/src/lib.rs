@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use parsing::ParseError;
+use search::bfs;
+use solution::Solution;
+
+pub struct Day06;
+
+// Every object directly or indirectly orbits everything between itself and
+// the center, so its depth in the tree is its number of orbits.
+fn count_orbits(orbit_tree: &HashMap<String, Vec<String>>, center: &str) -> usize {
+    let search = bfs(center.to_string(), |object| {
+        orbit_tree.get(object).cloned().unwrap_or_default()
+    }, |_object| false);
+
+    search.reached().map(|(_object, depth)| depth).sum()
+}
+
+// Orbital transfers needed to move from the object YOU orbit to the one SAN
+// orbits.
+fn count_transfers(orbit_tree: &HashMap<String, Vec<String>>) -> Option<usize> {
+    let mut neighbours = HashMap::<&str, Vec<&str>>::new();
+    for (center, satellites) in orbit_tree {
+        for satellite in satellites {
+            neighbours.entry(center).or_default().push(satellite);
+            neighbours.entry(satellite).or_default().push(center);
+        }
+    }
+
+    let search = bfs("YOU", |object| neighbours.get(object).cloned().unwrap_or_default(), |object| *object == "SAN");
+
+    search.goal_cost().map(|transfers| transfers - 2)
+}
+
+fn parse(input: &str) -> Result<HashMap<String, Vec<String>>, ParseError> {
+    let mut orbit_tree = HashMap::<String, Vec<String>>::new();
+
+    for mut line in parsing::lines(input) {
+        let center = line.word()?.to_string();
+        line.expect(")")?;
+        let satellite = line.word()?.to_string();
+        line.end()?;
+
+        // dbg!(&center, &satellite);
+        match orbit_tree.get_mut(&center) {
+            Some(satellites) => {
+                satellites.push(satellite);
+            }
+            None => {
+                orbit_tree.insert(center, vec!(satellite));
+            }
+        }
+    }
+
+    // dbg!(&orbit_tree);
+    return Ok(orbit_tree);
+}
+
+impl Solution for Day06 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_orbits(&parse(input)?, "COM").to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        match count_transfers(&parse(input)?) {
+            Some(transfers) => Ok(transfers.to_string()),
+            None => Ok("No path from YOU to SAN.".to_string()),
+        }
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_orbit_tree| ())
+    }
+}
@@ -0,0 +1,105 @@
+use intcode::run;
+use intcode::Context;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Topology {
+    Chain,
+    Feedback,
+}
+
+pub struct Circuit {
+    program: Vec<i64>,
+    topology: Topology,
+}
+
+// All orderings of `length` distinct phases taken from `phases`.
+fn arrangements(phases: &[i64], length: usize) -> Vec<Vec<i64>> {
+    if length == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for (index, phase) in phases.iter().enumerate() {
+        let mut rest = phases.to_vec();
+        rest.remove(index);
+
+        for mut arrangement in arrangements(&rest, length - 1) {
+            arrangement.insert(0, *phase);
+            result.push(arrangement);
+        }
+    }
+
+    result
+}
+
+impl Circuit {
+    pub fn new(program: &[i64], topology: Topology) -> Circuit {
+        Circuit {
+            program: program.to_vec(),
+            topology,
+        }
+    }
+
+    pub fn signal(&self, phases: &[i64]) -> i64 {
+        let mut contexts = phases.iter().map(|phase| {
+            let mut context = Context::new(&self.program);
+            context.inputs.push_back(*phase);
+            context
+        }).collect::<Vec<Context>>();
+
+        let mut input = 0;
+        if contexts.is_empty() {
+            return input;
+        }
+
+        match self.topology {
+            Topology::Chain => {
+                for context in contexts.iter_mut() {
+                    context.inputs.push_back(input);
+                    if let Some(output) = run(context) {
+                        input = output;
+                    }
+                }
+            }
+            Topology::Feedback => {
+                let mut amplifier = 0;
+                loop {
+                    let context = &mut contexts[amplifier % phases.len()];
+                    context.inputs.push_back(input);
+                    match run(context) {
+                        Some(output) => input = output,
+                        None => break,
+                    }
+                    amplifier += 1;
+                }
+            }
+        }
+
+        input
+    }
+
+    // Tries every ordering of `stages` phases from the phase set, split into
+    // one batch per core. Returns the best ordering and its signal.
+    pub fn best(&self, phases: &[i64], stages: usize) -> Result<(Vec<i64>, i64), String> {
+        if stages == 0 {
+            return Err("A circuit needs at least one amplifier.".to_string());
+        }
+        if stages > phases.len() {
+            return Err(format!("Not enough phases for {} stages.", stages));
+        }
+
+        let candidates = arrangements(phases, stages);
+        let threads = std::thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+        let batch_size = std::cmp::max(1, candidates.len().div_ceil(threads));
+
+        std::thread::scope(|scope| {
+            let workers = candidates.chunks(batch_size).map(|batch| {
+                scope.spawn(move || {
+                    batch.iter().map(|phases| (phases.clone(), self.signal(phases))).max_by_key(|(_, signal)| *signal)
+                })
+            }).collect::<Vec<_>>();
+
+            workers.into_iter().filter_map(|worker| worker.join().unwrap()).max_by_key(|(_, signal)| *signal)
+        }).ok_or_else(|| format!("Not enough phases for {} stages.", stages))
+    }
+}
@@ -0,0 +1,80 @@
+pub mod circuit;
+
+use circuit::Circuit;
+use circuit::Topology;
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day07;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+// The best phase ordering of the puzzle's five amplifiers and its signal.
+pub fn best_ordering(program: &[i64], topology: Topology) -> (Vec<i64>, i64) {
+    let phases = match topology {
+        Topology::Chain => (0..5).collect::<Vec<i64>>(),
+        Topology::Feedback => (5..10).collect::<Vec<i64>>(),
+    };
+
+    Circuit::new(program, topology).best(&phases, 5).unwrap()
+}
+
+impl Solution for Day07 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(best_ordering(&parse(input)?, Topology::Chain).1.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(best_ordering(&parse(input)?, Topology::Feedback).1.to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(source: &str) -> Vec<i64> {
+        source.trim().split(",").map(|token| token.parse::<i64>().unwrap()).collect()
+    }
+
+    fn best(source: &str, topology: Topology, phases: std::ops::Range<i64>) -> (Vec<i64>, i64) {
+        Circuit::new(&program(source), topology).best(&phases.collect::<Vec<i64>>(), 5).unwrap()
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(best(include_str!("../input.txt"), Topology::Chain, 0..5).1, 298586);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(best(include_str!("../input.txt"), Topology::Feedback, 5..10).1, 9246095);
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(best(include_str!("../examples/chain1.txt"), Topology::Chain, 0..5), (vec![4, 3, 2, 1, 0], 43210));
+        assert_eq!(best(include_str!("../examples/chain2.txt"), Topology::Chain, 0..5), (vec![0, 1, 2, 3, 4], 54321));
+        assert_eq!(best(include_str!("../examples/chain3.txt"), Topology::Chain, 0..5), (vec![1, 0, 4, 3, 2], 65210));
+        assert_eq!(best(include_str!("../examples/feedback1.txt"), Topology::Feedback, 5..10), (vec![9, 8, 7, 6, 5], 139629729));
+        assert_eq!(best(include_str!("../examples/feedback2.txt"), Topology::Feedback, 5..10), (vec![9, 7, 8, 5, 6], 18216));
+    }
+
+    #[test]
+    fn circuit_sizes() {
+        let circuit = Circuit::new(&program(include_str!("../examples/feedback1.txt")), Topology::Feedback);
+
+        assert_eq!(circuit.best(&[], 0), Err("A circuit needs at least one amplifier.".to_string()));
+        assert_eq!(circuit.best(&[5, 6], 0), Err("A circuit needs at least one amplifier.".to_string()));
+        assert_eq!(circuit.best(&[], 1), Err("Not enough phases for 1 stages.".to_string()));
+        assert_eq!(circuit.best(&[5, 6], 3), Err("Not enough phases for 3 stages.".to_string()));
+        assert_eq!(circuit.signal(&[]), 0);
+        assert!(circuit.best(&[9, 8], 1).is_ok());
+    }
+}
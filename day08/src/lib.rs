@@ -0,0 +1,108 @@
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day08;
+
+// The image's digits followed by its width and height, one per line.
+fn parse(input: &str) -> Result<(Vec<u32>, usize, usize), ParseError> {
+    let mut lines = parsing::lines(input);
+    let mut next_line = |what| lines.next().ok_or_else(|| ParseError::at_end(input, &format!("expected {}", what)));
+
+    let mut line = next_line("the image")?;
+    let image = line.digits()?;
+
+    let mut line = next_line("the width")?;
+    let width = line.number::<usize>()?;
+    line.end()?;
+
+    let mut line = next_line("the height")?;
+    let height = line.number::<usize>()?;
+    line.end()?;
+
+    match width.checked_mul(height) {
+        Some(size) if size > 0 && image.len() % size == 0 => Ok((image, width, height)),
+        _ => Err(ParseError::new(1, 1, &format!("{} digits do not make up layers of {}x{}", image.len(), width, height))),
+    }
+}
+
+// Returns the checksum of the layer with the fewest zeros and the decoded
+// image.
+fn decode((image, width, height): (Vec<u32>, usize, usize)) -> (usize, Vec<Vec<usize>>) {
+
+    let mut min_zeros = width * height;
+    let mut num_ones = 0;
+    let mut num_twos = 0;
+    let mut digits = image.iter();
+
+    let mut decoded_image = Vec::new();
+    for _y in 0..height {
+        let mut row = Vec::new();
+        for _x in 0..width {
+            row.push(2);
+        }
+        decoded_image.push(row);
+    }
+
+    for _layer in 0..(image.len() / (width * height)) {
+        let mut histogram = [0; 10];
+        for pixel in 0..(width * height) {
+            let digit = *digits.next().unwrap() as usize;
+            histogram[digit] += 1;
+
+            let x = pixel % width;
+            let y = pixel / width;
+
+            if decoded_image[y][x] == 2 {
+                decoded_image[y][x] = digit;
+            }
+        }
+
+        if histogram[0] < min_zeros {
+            min_zeros = histogram[0];
+            num_ones = histogram[1];
+            num_twos = histogram[2];
+        }
+    }
+
+    return (num_ones * num_twos, decoded_image);
+}
+
+impl Solution for Day08 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(decode(parse(input)?).0.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let (_, decoded_image) = decode(parse(input)?);
+
+        let image_string = decoded_image.iter().map(|row| {
+            row.iter().map(|pixel| {
+                match pixel {
+                    0 => " ".to_string(),
+                    1 => "#".to_string(),
+                    _ => " ".to_string(),
+                }
+            }).collect::<Vec<String>>().join("")
+        }).collect::<Vec<String>>().join("\n");
+
+        match ocr::decode_grid(&decoded_image, |pixel| *pixel == 1) {
+            Some(text) => Ok(text),
+            None => Ok(image_string),
+        }
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_image| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let (_, decoded_image) = decode(parse(include_str!("../examples/decode.txt")).unwrap());
+        assert_eq!(decoded_image, vec![vec![0, 1], vec![1, 0]]);
+    }
+}
@@ -0,0 +1,212 @@
+use intcode::driver::exchange;
+use intcode::Context;
+
+pub const TEST_MODE: i64 = 1;
+pub const SENSOR_BOOST_MODE: i64 = 2;
+
+// Name and parameter count of every instruction the VM has to support.
+const OPCODES: [(i64, &str, usize); 10] = [
+    (1, "add", 3),
+    (2, "multiply", 3),
+    (3, "input", 1),
+    (4, "output", 1),
+    (5, "jump-if-true", 2),
+    (6, "jump-if-false", 2),
+    (7, "less than", 3),
+    (8, "equals", 3),
+    (9, "adjust relative base", 1),
+    (99, "halt", 0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl Mode {
+    fn from_id(id: i64) -> Option<Mode> {
+        match id {
+            0 => Some(Mode::Position),
+            1 => Some(Mode::Immediate),
+            2 => Some(Mode::Relative),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Mode::Position => "position",
+            Mode::Immediate => "immediate",
+            Mode::Relative => "relative",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Fault {
+    pub value: i64,
+    pub opcode: i64,
+    pub name: &'static str,
+    pub modes: Vec<Mode>,
+}
+
+impl Fault {
+    // A reported value is an instruction header like 1201 or 203, so it
+    // carries the opcode and the modes of its parameters.
+    fn decode(value: i64) -> Option<Fault> {
+        if value < 0 {
+            return None;
+        }
+
+        let (opcode, name, parameters) = *OPCODES.iter().find(|(opcode, _, _)| *opcode == value % 100)?;
+        let modes = (0..parameters as u32).map(|parameter| {
+            Mode::from_id(value / 10i64.pow(parameter + 2) % 10)
+        }).collect::<Option<Vec<Mode>>>()?;
+
+        // digits beyond the last parameter make no sense
+        if value / 10i64.pow(parameters as u32 + 2) != 0 {
+            return None;
+        }
+
+        Some(Fault { value, opcode, name, modes })
+    }
+
+    fn describe(&self) -> String {
+        let modes = self.modes.iter().enumerate().map(|(index, mode)| {
+            format!("parameter {} in {} mode", index + 1, mode.name())
+        }).collect::<Vec<String>>();
+
+        if modes.is_empty() {
+            format!("{}: opcode {} ({})", self.value, self.opcode, self.name)
+        } else {
+            format!("{}: opcode {} ({}) with {}", self.value, self.opcode, self.name, modes.join(", "))
+        }
+    }
+}
+
+pub struct Report {
+    pub outputs: Vec<i64>,
+    pub faults: Vec<Fault>,
+    pub unknown: Vec<i64>,
+    pub keycode: Option<i64>,
+}
+
+impl Report {
+    // Every output before the final keycode names an instruction the
+    // program found to be malfunctioning.
+    pub fn interpret(outputs: Vec<i64>) -> Report {
+        let (keycode, reported) = match outputs.split_last() {
+            Some((keycode, reported)) => (Some(*keycode), reported.to_vec()),
+            None => (None, Vec::new()),
+        };
+
+        let mut faults = Vec::new();
+        let mut unknown = Vec::new();
+        for value in reported {
+            match Fault::decode(value) {
+                Some(fault) => faults.push(fault),
+                None => unknown.push(value),
+            }
+        }
+
+        Report { outputs, faults, unknown, keycode }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.keycode.is_some() && self.faults.is_empty() && self.unknown.is_empty()
+    }
+
+    pub fn verdict(&self) -> Vec<String> {
+        if self.passed() {
+            return vec![format!("all opcodes passed, keycode {}", self.keycode.unwrap())];
+        }
+
+        let mut lines = Vec::new();
+        if self.keycode.is_none() {
+            lines.push("no output, the program did not get to report anything".to_string());
+        }
+        for fault in &self.faults {
+            lines.push(format!("faulty {}", fault.describe()));
+        }
+        for value in &self.unknown {
+            lines.push(format!("faulty {}: not a valid instruction, the output itself may be broken", value));
+        }
+
+        // if every reported instruction uses the same mode, that mode is the
+        // likely culprit rather than the opcodes themselves
+        let modes = [Mode::Position, Mode::Immediate, Mode::Relative];
+        if let Some(mode) = modes.iter().find(|mode| {
+            !self.faults.is_empty() && self.faults.iter().all(|fault| fault.modes.contains(mode))
+        }) {
+            lines.push(format!("every faulty instruction uses {} mode, check how it is resolved", mode.name()));
+        }
+
+        lines
+    }
+}
+
+pub fn run(program: &[i64], mode: i64) -> Vec<i64> {
+    let mut context = Context::new(program);
+    let mut outputs = Vec::new();
+    let mut inputs = vec![mode];
+    while let Some(output) = exchange(&mut context, &inputs, 1) {
+        outputs.push(output[0]);
+        inputs.clear();
+    }
+
+    outputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let fault = Fault::decode(1201).unwrap();
+        assert_eq!((fault.opcode, fault.name), (1, "add"));
+        assert_eq!(fault.modes, vec![Mode::Relative, Mode::Immediate, Mode::Position]);
+
+        assert_eq!(Fault::decode(203).unwrap().modes, vec![Mode::Relative]);
+        assert_eq!(Fault::decode(99).unwrap().modes, vec![]);
+
+        // negative, unknown opcode, unknown mode, digits beyond the parameters
+        for value in [-1, 55, 301, 10099, 21104] {
+            assert_eq!(Fault::decode(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn verdict() {
+        let report = Report::interpret(vec![42]);
+        assert!(report.passed());
+        assert_eq!(report.verdict(), vec!["all opcodes passed, keycode 42"]);
+
+        let report = Report::interpret(vec![]);
+        assert!(!report.passed());
+        assert_eq!(report.verdict(), vec!["no output, the program did not get to report anything"]);
+
+        let report = Report::interpret(vec![1201, 203, 12345, 0]);
+        assert!(!report.passed());
+        assert_eq!((report.faults.len(), report.unknown.clone(), report.keycode), (2, vec![12345], Some(0)));
+        assert_eq!(report.verdict(), vec![
+            "faulty 1201: opcode 1 (add) with parameter 1 in relative mode, parameter 2 in immediate mode, parameter 3 in position mode",
+            "faulty 203: opcode 3 (input) with parameter 1 in relative mode",
+            "faulty 12345: not a valid instruction, the output itself may be broken",
+            "every faulty instruction uses relative mode, check how it is resolved",
+        ]);
+
+        // no shared mode to blame
+        let report = Report::interpret(vec![1, 204, 0]);
+        assert_eq!(report.verdict().len(), 2);
+    }
+
+    #[test]
+    fn run_echoes_mode() {
+        let report = Report::interpret(run(&[3, 0, 4, 0, 99], TEST_MODE));
+        assert_eq!(report.keycode, Some(TEST_MODE));
+        assert!(report.passed());
+    }
+}
@@ -0,0 +1,40 @@
+pub mod boost;
+
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day09;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+pub fn outputs(outputs: &[i64]) -> String {
+    outputs.iter().map(|output| output.to_string()).collect::<Vec<String>>().join("\n")
+}
+
+impl Solution for Day09 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(outputs(&boost::run(&parse(input)?, boost::TEST_MODE)))
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(outputs(&boost::run(&parse(input)?, boost::SENSOR_BOOST_MODE)))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        // the quine prints itself and the others are checked through examples/answers.txt
+        let quine = parse(include_str!("../examples/quine.txt")).unwrap();
+        assert_eq!(boost::run(&quine, boost::TEST_MODE), quine);
+    }
+}
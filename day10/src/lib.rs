@@ -0,0 +1,210 @@
+use geometry::Grid;
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day10;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Asteroid {
+    x: i32,
+    y: i32,
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    let mut a = a;
+    let mut b = b;
+
+    while b != 0 {
+        let tmp = b;
+        b = a % b;
+        a = tmp;
+    }
+
+    return a;
+}
+
+fn can_detect(station: &Asteroid, target: &Asteroid, asteroids: &Vec<Asteroid>) -> bool {
+    if target == station {
+        return false;
+    }
+
+    if target.x == station.x {
+        for obstacle in asteroids {
+            if obstacle == target || obstacle == station {
+                continue;
+            }
+
+            let same_half_y = (station.y - target.y).signum() == (station.y - obstacle.y).signum();
+
+            if !same_half_y {
+                continue;
+            }
+
+            if obstacle.x == target.x && (station.y - obstacle.y).abs() < (station.y - target.y).abs() {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    if target.y == station.y {
+        for obstacle in asteroids {
+            if obstacle == target || obstacle == station {
+                continue;
+            }
+
+            let same_half_x = (station.x - target.x).signum() == (station.x - obstacle.x).signum();
+
+            if !same_half_x {
+                continue;
+            }
+
+            if obstacle.y == target.y && (station.x - obstacle.x).abs() < (station.x - target.x).abs() {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    let target_distance_x = (station.x - target.x).abs() as u32;
+    let target_distance_y = (station.y - target.y).abs() as u32;
+
+    for obstacle in asteroids {
+        if obstacle == target || obstacle == station {
+            continue;
+        }
+
+        let same_half_x = (station.x - target.x).signum() == (station.x - obstacle.x).signum();
+        let same_half_y = (station.y - target.y).signum() == (station.y - obstacle.y).signum();
+
+        if !same_half_x || !same_half_y {
+            continue;
+        }
+
+        let obstacle_distance_x = (station.x - obstacle.x).abs() as u32;
+        let obstacle_distance_y = (station.y - obstacle.y).abs() as u32;
+
+        let target_distance_2 = target_distance_x * target_distance_x + target_distance_y * target_distance_y;
+        let obstacle_distance_2 = obstacle_distance_x * obstacle_distance_x + obstacle_distance_y * obstacle_distance_y;
+
+        if obstacle_distance_2 >= target_distance_2 {
+            continue;
+        }
+
+        let gcd_x = gcd(target_distance_x, obstacle_distance_x);
+        let gcd_y = gcd(target_distance_y, obstacle_distance_y);
+
+        let obstacle_on_line = obstacle_distance_x / gcd_x == obstacle_distance_y / gcd_y;
+        let target_on_line = target_distance_x / gcd_x == target_distance_y / gcd_y;
+
+        if obstacle_on_line && target_on_line {
+            return false;
+        }
+    }
+
+    return true;
+}
+
+fn parse(input: &str) -> Result<Vec<Asteroid>, ParseError> {
+    let map = Grid::try_parse(input, |_position, character| match character {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+
+    let mut asteroids = Vec::<Asteroid>::new();
+    for (position, _) in map.iter().filter(|(_position, asteroid)| **asteroid) {
+        asteroids.push(Asteroid { x: position.x as i32, y: position.y as i32 });
+    }
+
+    if asteroids.is_empty() {
+        return Err(ParseError::new(1, 1, "the map has no asteroids"));
+    }
+
+    return Ok(asteroids);
+}
+
+// The asteroid that detects the most others, and how many it detects.
+fn best_station(asteroids: &Vec<Asteroid>) -> (&Asteroid, u32) {
+    let mut max_detected = 0;
+    let mut argmax = &asteroids[0];
+    for station in asteroids {
+        let mut detected = 0;
+
+        for target in asteroids {
+            if can_detect(station, target, asteroids) {
+                detected += 1;
+            }
+        }
+
+        if detected > max_detected {
+            max_detected = detected;
+            argmax = station;
+        }
+    }
+
+    return (argmax, max_detected);
+}
+
+// The asteroids in sight of the station, in the order in which the laser
+// sweeps over them: clockwise, starting straight up.
+fn vaporization_order(station: &Asteroid, asteroids: &Vec<Asteroid>) -> Vec<Asteroid> {
+    let mut detectable = Vec::<Asteroid>::new();
+    for target in asteroids {
+        if can_detect(station, target, asteroids) {
+            detectable.push((*target).clone());
+        }
+    }
+
+    detectable.sort_by(|a, b| {
+        let a_x = (a.y - station.y) as f64;
+        let a_y = (a.x - station.x) as f64;
+        let score_a = std::f64::consts::PI - (a_y).atan2(a_x);
+
+        let b_x = (b.y - station.y) as f64;
+        let b_y = (b.x - station.x) as f64;
+        let score_b = std::f64::consts::PI - (b_y).atan2(b_x);
+
+        score_a.partial_cmp(&score_b).unwrap()
+    });
+
+    detectable
+}
+
+impl Solution for Day10 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(best_station(&parse(input)?).1.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let asteroids = parse(input)?;
+        let (argmax, _) = best_station(&asteroids);
+
+        match vaporization_order(argmax, &asteroids).get(199) {
+            Some(asteroid) => Ok((asteroid.x * 100 + asteroid.y).to_string()),
+            None => Ok("Fewer than 200 asteroids are in sight.".to_string()),
+        }
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_asteroids| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vaporization() {
+        let asteroids = parse(include_str!("../examples/vaporization.txt")).unwrap();
+        let order = vaporization_order(&Asteroid { x: 8, y: 3 }, &asteroids);
+        let expected = [(8, 1), (9, 0), (9, 1), (10, 0), (9, 2), (11, 1), (12, 1), (11, 2), (15, 1)];
+
+        for (asteroid, &(x, y)) in order.iter().zip(expected.iter()) {
+            assert_eq!(asteroid, &Asteroid { x, y });
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use geometry::Direction;
+use geometry::Position;
+use geometry::SparseGrid;
+use intcode::driver::drive;
+use intcode::driver::Control;
+use intcode::Context;
+use parsing::ParseError;
+use solution::Solution;
+use visualize::Frame;
+use visualize::BLACK;
+use visualize::WHITE;
+
+pub struct Day11;
+
+// The robot reports 0 for a left turn and 1 for a right turn.
+fn turn(orientation: &Direction, turn_direction: i64) -> Direction {
+    match turn_direction {
+        0 => orientation.left_hand_side(),
+        1 => orientation.right_hand_side(),
+        _ => orientation.clone(),
+    }
+}
+
+pub fn paint(program: &[i64], panels: &mut SparseGrid<i64>) -> HashSet<Position> {
+    let mut context = Context::new(program);
+    let mut position = Position { x: 0, y: 0 };
+    let mut orientation = Direction::Up;
+    let mut painted = HashSet::new();
+
+    // the robot reports a colour and a turn, and is fed the panel it ends up on
+    let start = *panels.get(&position).unwrap_or(&0);
+    drive(&mut context, &[start], 2, |outputs| {
+        panels.insert(position.clone(), outputs[0]);
+        painted.insert(position.clone());
+
+        orientation = turn(&orientation, outputs[1]);
+        position = position.step(&orientation);
+        Control::Feed(vec![*panels.get(&position).unwrap_or(&0)])
+    });
+
+    painted
+}
+
+// '#' is a white panel, anything else a black one. The robot starts on the
+// top left panel.
+pub fn parse_hull(text: &str) -> SparseGrid<i64> {
+    let mut panels = SparseGrid::new();
+    for (y, line) in text.lines().enumerate() {
+        for (x, panel) in line.chars().enumerate() {
+            let color = if panel == '#' { 1 } else { 0 };
+            panels.insert(Position { x: x as i64, y: y as i64 }, color);
+        }
+    }
+
+    panels
+}
+
+pub fn load_hull(path: &str) -> std::io::Result<SparseGrid<i64>> {
+    Ok(parse_hull(&std::fs::read_to_string(path)?))
+}
+
+pub fn render(panels: &SparseGrid<i64>) -> String {
+    panels.render(|color| if color == Some(&1) { '#' } else { ' ' })
+}
+
+// A PBM bitmap, or a PGM graymap if `graymap` is set. Each panel becomes a
+// `scale` x `scale` block.
+fn encode(panels: &SparseGrid<i64>, scale: usize, graymap: bool) -> Vec<u8> {
+    let bounds = panels.bounds().unwrap();
+    let white = |x: usize, y: usize| {
+        let position = Position { x: (x / scale) as i64 + bounds.min.x, y: (y / scale) as i64 + bounds.min.y };
+        panels.get(&position) == Some(&1)
+    };
+    let width = bounds.width() as usize * scale;
+    let height = bounds.height() as usize * scale;
+
+    let mut image = Vec::new();
+    if graymap {
+        image.extend(format!("P5\n{} {}\n255\n", width, height).into_bytes());
+        for y in 0..height {
+            for x in 0..width {
+                image.push(if white(x, y) { 255 } else { 0 });
+            }
+        }
+    } else {
+        // PBM rows are packed eight pixels to a byte, and 1 means black
+        image.extend(format!("P4\n{} {}\n", width, height).into_bytes());
+        for y in 0..height {
+            for byte in 0..width.div_ceil(8) {
+                let bits = (0..8).filter(|bit| {
+                    let x = byte * 8 + bit;
+                    x < width && !white(x, y)
+                }).fold(0u8, |bits, bit| bits | (0x80 >> bit));
+                image.push(bits);
+            }
+        }
+    }
+
+    image
+}
+
+// Writes a PGM graymap for a `.pgm` path and a PBM bitmap otherwise.
+pub fn export(path: &str, panels: &SparseGrid<i64>, scale: usize) -> std::io::Result<()> {
+    if scale == 0 {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "the scale has to be at least 1"));
+    }
+    if panels.is_empty() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "there are no panels to export"));
+    }
+
+    std::fs::write(path, encode(panels, scale, path.ends_with(".pgm")))
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+// Paints the hull starting on a single white panel.
+pub fn registration(program: &[i64]) -> SparseGrid<i64> {
+    let mut panels = SparseGrid::new();
+    panels.insert(Position { x: 0, y: 0 }, 1);
+    paint(program, &mut panels);
+
+    panels
+}
+
+impl Solution for Day11 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let mut panels = SparseGrid::new();
+
+        Ok(paint(&parse(input)?, &mut panels).len().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let panels = registration(&parse(input)?);
+
+        let painted = panels.iter().filter(|(_position, color)| **color == 1).map(|(position, _color)| {
+            (position.x, position.y)
+        });
+        match ocr::decode_points(painted) {
+            Some(text) => Ok(text),
+            None => Ok(render(&panels)),
+        }
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+
+    // The registration identifier as painted.
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        let panels = registration(&parse(input)?);
+        frame(Frame::from_sparse(&panels, |color| if color == Some(&1) { WHITE } else { BLACK }));
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Halts on a white panel, otherwise paints it white and turns right.
+    const PAINTER: [i64; 13] = [3, 100, 1005, 100, 12, 104, 1, 104, 1, 1105, 1, 0, 99];
+
+    #[test]
+    fn hull() {
+        let panels = parse_hull("#.\n.#x\n");
+        assert_eq!(panels.len(), 5);
+        assert_eq!(panels.get(&Position { x: 0, y: 0 }), Some(&1));
+        assert_eq!(panels.get(&Position { x: 1, y: 1 }), Some(&1));
+        assert_eq!(panels.get(&Position { x: 2, y: 1 }), Some(&0));
+        assert_eq!(render(&panels), "#  \n # ");
+
+        assert!(load_hull("no-such-hull.txt").is_err());
+    }
+
+    #[test]
+    fn paint_loaded_hull() {
+        // the robot circles clockwise through the four top left panels
+        let mut panels = SparseGrid::new();
+        assert_eq!(paint(&PAINTER, &mut panels).len(), 4);
+
+        let mut panels = parse_hull("..\n.#\n");
+        assert_eq!(paint(&PAINTER, &mut panels).len(), 2);
+        assert_eq!(panels.get(&Position { x: 1, y: 0 }), Some(&1));
+        assert_eq!(panels.get(&Position { x: 0, y: 1 }), Some(&0));
+    }
+
+    #[test]
+    fn bitmap() {
+        // ten panels wide, so each row takes two bytes
+        let panels = parse_hull("#.#.#.#.#.\n..........\n");
+        let image = encode(&panels, 1, false);
+
+        assert_eq!(&image[..8], b"P4\n10 2\n");
+        assert_eq!(&image[8..], &[0b0101_0101, 0b0100_0000, 0b1111_1111, 0b1100_0000]);
+    }
+
+    #[test]
+    fn graymap() {
+        let panels = parse_hull("#.\n");
+        let image = encode(&panels, 2, true);
+
+        assert_eq!(&image[..11], b"P5\n4 2\n255\n");
+        assert_eq!(&image[11..], &[255, 255, 0, 0, 255, 255, 0, 0]);
+    }
+
+    #[test]
+    fn export_errors() {
+        let panels = parse_hull("#\n");
+        assert!(export("unused.pbm", &panels, 0).is_err());
+        assert!(export("unused.pbm", &SparseGrid::new(), 1).is_err());
+        assert!(export("no-such-directory/hull.pbm", &panels, 1).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+pub mod memory;
+
+use geometry::Position;
+use geometry::SparseGrid;
+use intcode::driver::drive;
+use intcode::driver::Control;
+use intcode::Context;
+use parsing::ParseError;
+use solution::Solution;
+use visualize::Frame;
+use visualize::Rgb;
+
+pub struct Day13;
+
+fn display(game: &SparseGrid<i64>) {
+    let snapshot = game.render(|tile_id| match tile_id {
+        Some(1) => '#',
+        Some(2) => 'O',
+        Some(3) => '=',
+        Some(4) => '*',
+        _ => ' ',
+    });
+
+    println!("{}", snapshot);
+}
+
+fn palette(tile_id: Option<&i64>) -> Rgb {
+    match tile_id {
+        Some(1) => [128, 128, 128],
+        Some(2) => [64, 160, 255],
+        Some(3) => [255, 255, 255],
+        Some(4) => [255, 80, 48],
+        _ => [0, 0, 0],
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+// Plays the game to the end, moving the paddle under the ball, and returns
+// the final score.
+pub fn play(program: &[i64]) -> i64 {
+    play_watched(program, |_game| {})
+}
+
+// Like `play`, showing the screen to `watch` whenever the score is updated,
+// which is once at the start and then for every block that breaks.
+fn play_watched<F>(program: &[i64], mut watch: F) -> i64 where F: FnMut(&SparseGrid<i64>) {
+    let mut context = Context::new(program);
+    context.program[0] = 2;
+
+    let mut game = SparseGrid::new();
+    let mut score = 0;
+    let mut paddle_position: Option<Position> = None;
+    let mut track_ball = false;
+    let mut controller = |outputs: &[i64]| {
+        let (x, y, tile_id) = (outputs[0], outputs[1], outputs[2]);
+        if x == -1 && y == 0 {
+            score = tile_id;
+            watch(&game);
+            return Control::Feed(vec![]);
+        }
+
+        game.insert(Position { x, y }, tile_id);
+        match tile_id {
+            3 => paddle_position = Some(Position { x, y }),
+            4 => {
+                if let Some(paddle) = &paddle_position {
+                    match x.cmp(&paddle.x) {
+                        std::cmp::Ordering::Equal => track_ball = true,
+                        std::cmp::Ordering::Less if track_ball => return Control::Feed(vec![-1]),
+                        std::cmp::Ordering::Greater if track_ball => return Control::Feed(vec![1]),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Control::Feed(vec![])
+    };
+
+    // the joystick stays in the neutral position whenever the program reads
+    // it without the paddle having to move
+    loop {
+        drive(&mut context, &[], 3, &mut controller);
+        if !context.blocked {
+            break;
+        }
+        context.inputs.push_back(0);
+    }
+
+    score
+}
+
+impl Solution for Day13 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let program = parse(input)?;
+
+        let mut game = SparseGrid::new();
+        drive(&mut Context::new(&program), &[], 3, |outputs| {
+            game.insert(Position { x: outputs[0], y: outputs[1] }, outputs[2]);
+            Control::Feed(vec![])
+        });
+
+        let blocks = game.values().fold(0, |acc, tile_id| {
+            if *tile_id == 2 {
+                acc + 1
+            } else {
+                acc
+            }
+        });
+
+        Ok(blocks.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(play(&parse(input)?).to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        play_watched(&parse(input)?, |game| frame(Frame::from_sparse(game, palette)));
+        Ok(true)
+    }
+}
@@ -0,0 +1,261 @@
+use geometry::Position;
+use geometry::SparseGrid;
+use intcode::driver::exchange;
+use intcode::run;
+use intcode::Context;
+
+pub struct Layout {
+    pub width: i64,
+    pub height: i64,
+    pub board: usize,
+    pub score_table: usize,
+    pub score: usize,
+    pub ball: (usize, usize),
+    pub paddle: usize,
+    pub lookup: usize,
+    halt: usize,
+}
+
+fn instruction_length(opcode: i64) -> Option<usize> {
+    match opcode % 100 {
+        1 | 2 | 7 | 8 => Some(4),
+        5 | 6 => Some(3),
+        3 | 4 | 9 => Some(2),
+        99 => Some(1),
+        _ => None,
+    }
+}
+
+// Linear sweep over the code section. Anything that does not decode is skipped
+// one cell at a time, which is good enough to resync after inline data.
+fn instructions(program: &[i64], end: usize) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut pc = 0;
+    while pc < end {
+        match instruction_length(program[pc]) {
+            Some(length) => {
+                starts.push(pc);
+                pc += length;
+            }
+            None => {
+                pc += 1;
+            }
+        }
+    }
+
+    starts
+}
+
+fn next_triple(context: &mut Context) -> Option<(i64, i64, i64)> {
+    let outputs = exchange(context, &[], 3)?;
+
+    Some((outputs[0], outputs[1], outputs[2]))
+}
+
+fn find_board(program: &[i64], board: &SparseGrid<i64>, width: i64, height: i64) -> Option<usize> {
+    let cells = (0..height).flat_map(|y| {
+        (0..width).map(move |x| Position { x, y })
+    }).map(|position| *board.get(&position).unwrap_or(&0)).collect::<Vec<i64>>();
+
+    program.windows(cells.len()).position(|window| window == &cells[..])
+}
+
+// Memory cells in the code section that hold `before` in the first snapshot
+// and `after` in the second.
+fn track(first: &[i64], second: &[i64], end: usize, before: i64, after: i64) -> Option<usize> {
+    let candidates = (0..end).filter(|address| {
+        first[*address] == before && second[*address] == after
+    }).collect::<Vec<usize>>();
+
+    if candidates.len() == 1 {
+        return Some(candidates[0]);
+    }
+
+    None
+}
+
+pub fn locate(program: &[i64]) -> Option<Layout> {
+    let mut context = Context::new(program);
+    context.program[0] = 2;
+
+    let mut board = SparseGrid::new();
+    let mut ball = None;
+    let mut paddle = None;
+    loop {
+        let (x, y, tile_id) = next_triple(&mut context)?;
+        if x == -1 && y == 0 {
+            break;
+        }
+
+        match tile_id {
+            3 => paddle = Some(Position { x, y }),
+            4 => ball = Some(Position { x, y }),
+            _ => {}
+        }
+        board.insert(Position { x, y }, tile_id);
+    }
+    let ball = ball?;
+    let paddle = paddle?;
+
+    let bounds = board.bounds()?;
+    let width = bounds.max.x + 1;
+    let height = bounds.max.y + 1;
+
+    // the first frame is exactly the initial board, so it can be matched
+    // against the program image directly
+    let board_start = find_board(program, &board, width, height)?;
+    let score_table = board_start + (width * height) as usize;
+    let starts = instructions(program, board_start);
+
+    // the score is drawn as "out -1, out 0, out [score]"
+    let score = starts.iter().find_map(|pc| {
+        if program[*pc..*pc + 6] == [104, -1, 104, 0, 4, program[*pc + 5]] {
+            Some(program[*pc + 5] as usize)
+        } else {
+            None
+        }
+    })?;
+
+    // the lookup routine is the one that adds the table base as an immediate
+    // to the index it computed; it starts at the closest preceding frame setup
+    let reference = *starts.iter().find(|pc| {
+        let opcode = program[**pc];
+        opcode % 100 == 1 && (1..=2).any(|offset| {
+            opcode / 10i64.pow(offset + 1) % 10 == 1 && program[**pc + offset as usize] == score_table as i64
+        })
+    })?;
+    let lookup = *starts.iter().rev().find(|pc| {
+        **pc < reference && program[**pc] == 109 && program[**pc + 1] > 0
+    })?;
+
+    let halt = *starts.iter().find(|pc| program[**pc] == 99)?;
+
+    // watch the ball and paddle move one frame with the joystick tilted right
+    let first = context.program.clone();
+    context.inputs.push_back(1);
+    let mut moved_ball = None;
+    let mut moved_paddle = None;
+    while moved_ball.is_none() {
+        let (x, y, tile_id) = next_triple(&mut context)?;
+        match tile_id {
+            3 => moved_paddle = Some(Position { x, y }),
+            4 => moved_ball = Some(Position { x, y }),
+            _ => {}
+        }
+    }
+    let moved_ball = moved_ball?;
+    let moved_paddle = moved_paddle?;
+    let second = context.program.clone();
+
+    let ball_x = track(&first, &second, board_start, ball.x, moved_ball.x)?;
+    let ball_y = track(&first, &second, board_start, ball.y, moved_ball.y)?;
+    let paddle_x = track(&first, &second, board_start, paddle.x, moved_paddle.x)?;
+
+    Some(Layout {
+        width,
+        height,
+        board: board_start,
+        score_table,
+        score,
+        ball: (ball_x, ball_y),
+        paddle: paddle_x,
+        lookup,
+        halt,
+    })
+}
+
+// Calls the program's own lookup routine with (x, y) on a scratch stack.
+// Arguments go to rb[1..], the return address to rb[0] and the result comes
+// back in rb[1]. A routine that draws something is not the lookup routine.
+fn score_address(program: &[i64], layout: &Layout, position: &Position) -> Option<usize> {
+    let mut context = Context::new(program);
+    let stack = program.len() + 1024;
+    context.relative_base = stack;
+    context.poke(stack, layout.halt as i64);
+    context.poke(stack + 1, position.x);
+    context.poke(stack + 2, position.y);
+    context.pc = layout.lookup;
+
+    if run(&mut context).is_some() {
+        return None;
+    }
+
+    Some(context.peek(stack + 1) as usize)
+}
+
+// None if the lookup routine turns out not to be one.
+pub fn final_score(program: &[i64], layout: &Layout) -> Option<i64> {
+    let mut score = 0;
+    for y in 0..layout.height {
+        for x in 0..layout.width {
+            let tile_id = program[layout.board + (y * layout.width + x) as usize];
+            if tile_id == 2 {
+                let address = score_address(program, layout, &Position { x, y })?;
+                score += program.get(address)?;
+            }
+        }
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program() -> Vec<i64> {
+        crate::parse(include_str!("../input.txt")).unwrap()
+    }
+
+    #[test]
+    fn sweep() {
+        assert_eq!(instruction_length(1002), Some(4));
+        assert_eq!(instruction_length(1105), Some(3));
+        assert_eq!(instruction_length(204), Some(2));
+        assert_eq!(instruction_length(99), Some(1));
+        assert_eq!(instruction_length(42), None);
+
+        // the 42 is inline data that is skipped
+        assert_eq!(instructions(&[1101, 1, 2, 3, 42, 104, 0, 99], 8), vec![0, 5, 7]);
+    }
+
+    #[test]
+    fn snapshots() {
+        let first = vec![5, 3, 5, 7];
+        let second = vec![6, 3, 5, 7];
+        assert_eq!(track(&first, &second, 4, 5, 6), Some(0));
+        assert_eq!(track(&first, &second, 4, 3, 3), Some(1));
+        assert_eq!(track(&first, &second, 4, 5, 5), Some(2));
+        assert_eq!(track(&first, &second, 4, 7, 8), None);
+        assert_eq!(track(&[1, 1], &[2, 2], 2, 1, 2), None);
+
+        let mut board = SparseGrid::new();
+        board.insert(Position { x: 1, y: 0 }, 2);
+        board.insert(Position { x: 0, y: 1 }, 1);
+        assert_eq!(find_board(&[9, 0, 2, 1, 0, 9], &board, 2, 2), Some(1));
+        assert_eq!(find_board(&[9, 0, 2, 0, 1, 9], &board, 2, 2), None);
+    }
+
+    #[test]
+    fn game_state() {
+        let program = program();
+        let layout = locate(&program).unwrap();
+
+        assert_eq!((layout.board, layout.width, layout.height), (639, 40, 26));
+        assert_eq!(layout.score_table, 1679);
+        assert_eq!(layout.score, 386);
+        assert_eq!(layout.ball, (388, 389));
+        assert_eq!(layout.paddle, 392);
+        assert_eq!(layout.lookup, 601);
+        assert_eq!(final_score(&program, &layout), Some(13956));
+    }
+
+    #[test]
+    fn lookup_routine_that_draws() {
+        let program = vec![104, 5, 99];
+        let layout = Layout { width: 1, height: 1, board: 1, score_table: 2, score: 0, ball: (0, 0), paddle: 0, lookup: 0, halt: 2 };
+
+        assert_eq!(score_address(&program, &layout, &Position { x: 0, y: 0 }), None);
+        assert_eq!(final_score(&[104, 2, 99], &layout), None);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use parsing::ParseError;
+use parsing::Scanner;
+use solution::Solution;
+
+pub struct Day14;
+
+#[derive(Debug)]
+struct Chemical {
+    name: String,
+    amount: u64,
+}
+
+#[derive(Debug)]
+struct Reaction {
+    inputs: Vec<Chemical>,
+    output: Chemical,
+}
+
+fn producible_amount(a: u64, b: u64) -> (u64, u64) {
+    let floor = a / b;
+    if a % b == 0 {
+        (floor, 0)
+    } else {
+        let ceil = floor + 1;
+        let producible = ceil * b;
+        (ceil, producible - a)
+    }
+}
+
+fn produce(request: &Chemical, reactions: &HashMap<String, Reaction>, spares: &mut HashMap<String, u64>, depth: u8) -> u64 {
+    // let indentation = (0..depth).map(|_| "  ").collect::<String>();
+
+    // println!("{}Producing {} {}", &indentation, &request.amount, &request.name);
+    // dbg!(&spares);
+
+    if request.name == "ORE" {
+        return request.amount;
+    }
+
+    let request_reaction = reactions.get(&request.name).unwrap();
+    let actual_amount = producible_amount(request.amount, request_reaction.output.amount);
+    if actual_amount.1 > 0 {
+        // println!("{}Produced {} spare {}", &indentation, &actual_amount.1, &request.name);
+    }
+    *spares.entry(request.name.clone()).or_insert(0) += actual_amount.1;
+    let mut total_ore = 0;
+    for input in &request_reaction.inputs {
+        if input.name == "ORE" {
+            let total_input = Chemical {
+                name: input.name.clone(),
+                amount: input.amount * actual_amount.0,
+            };
+            total_ore += produce(&total_input, reactions, spares, depth + 1);
+            continue;
+        }
+
+        let available_amount = *spares.entry(input.name.clone()).or_insert(0);
+        if input.amount * actual_amount.0 > available_amount {
+            let total_input = Chemical {
+                name: input.name.clone(),
+                amount: input.amount * actual_amount.0 - available_amount,
+            };
+            if available_amount > 0 {
+                // println!("{}Using {} spare {}", &indentation, &available_amount, &input.name);
+                *spares.entry(input.name.clone()).or_insert(0) = 0;
+            }
+            total_ore += produce(&total_input, reactions, spares, depth + 1);
+        } else {
+            *spares.entry(input.name.clone()).or_insert(0) -= input.amount * actual_amount.0;
+        }
+    }
+
+    return total_ore;
+}
+
+fn parse_chemical(line: &mut Scanner) -> Result<Chemical, ParseError> {
+    let amount = match line.number::<u64>()? {
+        0 => return Err(line.error("amounts must not be zero")),
+        amount => amount,
+    };
+    line.expect(" ")?;
+    let name = line.word()?.to_string();
+
+    Ok(Chemical { name, amount })
+}
+
+// One reaction per line, as in `7 A, 1 E => 1 FUEL`. Every chemical but ORE
+// has to be produced by some reaction.
+fn parse(input: &str) -> Result<HashMap<String, Reaction>, ParseError> {
+    let mut reactions = HashMap::new();
+    let mut consumed = Vec::new();
+    for mut line in parsing::lines(input) {
+        let mut inputs = Vec::new();
+        loop {
+            let column = line.column();
+            let chemical = parse_chemical(&mut line)?;
+            consumed.push((chemical.name.clone(), line.line(), column));
+            inputs.push(chemical);
+            if !line.accept(", ") {
+                break;
+            }
+        }
+        line.expect(" => ")?;
+        let column = line.column();
+        let output = parse_chemical(&mut line)?;
+        line.end()?;
+
+        if reactions.contains_key(&output.name) {
+            return Err(ParseError::new(line.line(), column, &format!("{} is produced by two reactions", output.name)));
+        }
+
+        let reaction = Reaction { inputs, output };
+
+        // dbg!(&reaction);
+        reactions.insert(reaction.output.name.clone(), reaction);
+    }
+
+    // dbg!(&reactions);
+
+    for (name, line, column) in consumed {
+        if name != "ORE" && !reactions.contains_key(&name) {
+            return Err(ParseError::new(line, column, &format!("no reaction produces {}", name)));
+        }
+    }
+
+    if !reactions.contains_key("FUEL") {
+        return Err(ParseError::at_end(input, "no reaction produces FUEL"));
+    }
+
+    return Ok(reactions);
+}
+
+impl Solution for Day14 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let reactions = parse(input)?;
+
+        let mut available = HashMap::new();
+        let desired = Chemical {
+            name: "FUEL".to_string(),
+            amount: 1,
+        };
+        Ok(produce(&desired, &reactions, &mut available, 0).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let reactions = parse(input)?;
+
+        let mut factor = 100_000_000 as u64;
+        let mut fuel = factor;
+        loop {
+            // dbg!(fuel);
+            let mut available = HashMap::new();
+            let desired = Chemical {
+                name: "FUEL".to_string(),
+                amount: fuel,
+            };
+            let ore_needed =  produce(&desired, &reactions, &mut available, 0);
+
+            if ore_needed > 1_000_000_000_000 {
+                if factor == 1 {
+                    break;
+                }
+
+                fuel -= factor;
+                factor /= 10;
+            }
+
+            fuel += factor;
+        }
+        Ok((fuel - 1).to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_reactions| ())
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use geometry::Direction;
+use geometry::Position;
+use geometry::SparseGrid;
+use intcode::driver::exchange;
+use intcode::driver::Machine;
+use intcode::Context;
+use parsing::ParseError;
+use search::bfs;
+use solution::Solution;
+use visualize::Format;
+use visualize::Frame;
+use visualize::Recorder;
+use visualize::Rgb;
+use visualize::BLACK;
+
+pub struct Day15;
+
+// Movement commands as the repair droid expects them.
+fn command(direction: &Direction) -> i64 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+#[derive(Debug)]
+enum Status {
+    HitWall,
+    Moved,
+    FoundTarget,
+}
+
+impl Status {
+    fn from_id(id: i64) -> Status {
+        match id {
+            0 => Status::HitWall,
+            1 => Status::Moved,
+            2 => Status::FoundTarget,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum MapTile {
+    Robot,
+    Wall,
+    Floor,
+    Start,
+    Target,
+    Oxygen,
+}
+
+impl MapTile {
+    fn to_rgb(&self) -> Rgb {
+        match self {
+            MapTile::Robot => [255, 0, 0],
+            MapTile::Wall => [64, 64, 64],
+            MapTile::Floor => [224, 224, 224],
+            MapTile::Start => [0, 160, 0],
+            MapTile::Target => [255, 160, 0],
+            MapTile::Oxygen => [0, 96, 255],
+        }
+    }
+}
+
+fn display(maze: &SparseGrid<MapTile>) {
+    let snapshot = maze.render(|tile| match tile {
+        Some(MapTile::Robot) => '*',
+        Some(MapTile::Wall) => '#',
+        Some(MapTile::Target) => 'X',
+        Some(MapTile::Start) => 'O',
+        Some(MapTile::Floor) => ' ',
+        Some(MapTile::Oxygen) => 'o',
+        None => ' ',
+    });
+
+    println!("{}\n---", snapshot);
+}
+
+fn picture(maze: &SparseGrid<MapTile>) -> Frame {
+    Frame::from_sparse(maze, |tile| match tile {
+        Some(tile) => tile.to_rgb(),
+        None => BLACK,
+    })
+}
+
+fn open_neighbours(maze: &SparseGrid<MapTile>, position: &Position) -> Vec<Position> {
+    position.neighbours().into_iter().filter(|neighbour| maze.get(neighbour) != Some(&MapTile::Wall)).collect()
+}
+
+pub fn flood_fill(maze: &SparseGrid<MapTile>, source: &Position) -> HashMap<Position, i64> {
+    let search = bfs(source.clone(), |position| open_neighbours(maze, position), |_position| false);
+
+    search.reached().map(|(position, minutes)| (position.clone(), *minutes as i64)).collect()
+}
+
+fn oxygen_frame(maze: &SparseGrid<MapTile>, fill_times: &HashMap<Position, i64>, minute: i64) -> SparseGrid<MapTile> {
+    let mut frame = maze.clone();
+    for (position, time) in fill_times {
+        if *time <= minute {
+            frame.insert(position.clone(), MapTile::Oxygen);
+        }
+    }
+
+    frame
+}
+
+pub fn animate(maze: &SparseGrid<MapTile>, fill_times: &HashMap<Position, i64>) {
+    let duration = *fill_times.values().max().unwrap();
+    for minute in 0..=duration {
+        print!("\x1b[2J\x1b[H");
+        display(&oxygen_frame(maze, fill_times, minute));
+        println!("minute {}", minute);
+        std::thread::sleep(std::time::Duration::from_millis(30));
+    }
+}
+
+pub fn export_frames(directory: &str, maze: &SparseGrid<MapTile>, fill_times: &HashMap<Position, i64>, scale: usize) -> std::io::Result<()> {
+    let mut recorder = Recorder::new(Path::new(directory), Format::Ppm, scale)?;
+
+    let duration = *fill_times.values().max().unwrap();
+    for minute in 0..=duration {
+        recorder.record(&picture(&oxygen_frame(maze, fill_times, minute)))?;
+    }
+
+    Ok(())
+}
+
+fn shortest_path(maze: &SparseGrid<MapTile>, start: &Position, target: &Position) -> Option<usize> {
+    bfs(start.clone(), |position| open_neighbours(maze, position), |position| position == target).goal_cost()
+}
+
+fn send<M>(droid: &mut M, direction: &Direction, cost: &mut usize) -> Option<Status> where M: Machine {
+    *cost += 1;
+
+    exchange(droid, &[command(direction)], 1).map(|outputs| Status::from_id(outputs[0]))
+}
+
+fn explore_from<M>(droid: &mut M, maze: &mut SparseGrid<MapTile>, position: &Position, cost: &mut usize) -> Result<(), String> where M: Machine {
+    for direction in Direction::all() {
+        let new_position = position.step(&direction);
+        if maze.contains(&new_position) {
+            continue;
+        }
+
+        match send(droid, &direction, cost) {
+            Some(Status::HitWall) => {
+                maze.insert(new_position, MapTile::Wall);
+            }
+            Some(status) => {
+                match status {
+                    Status::FoundTarget => maze.insert(new_position.clone(), MapTile::Target),
+                    _ => maze.insert(new_position.clone(), MapTile::Floor),
+                };
+
+                explore_from(droid, maze, &new_position, cost)?;
+
+                match send(droid, &direction.opposite(), cost) {
+                    Some(Status::HitWall) => return Err("The droid could not walk back.".to_string()),
+                    Some(_) => {}
+                    None => return Err("The droid stopped before the area was explored.".to_string()),
+                }
+            }
+            None => return Err("The droid stopped before the area was explored.".to_string()),
+        }
+    }
+
+    Ok(())
+}
+
+// Depth-first search that walks the droid back after every branch, so every
+// reachable cell gets visited no matter how many loops the maze has.
+fn explore<M>(droid: &mut M) -> Result<(SparseGrid<MapTile>, usize), String> where M: Machine {
+    let mut maze = SparseGrid::new();
+    let mut cost = 0;
+    let start = Position { x: 0, y: 0 };
+    maze.insert(start.clone(), MapTile::Start);
+    explore_from(droid, &mut maze, &start, &mut cost)?;
+
+    Ok((maze, cost))
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+// Explores the whole area and returns it together with the oxygen system and
+// the number of moves the exploration took.
+pub fn survey(program: &[i64]) -> Result<(SparseGrid<MapTile>, Position, usize), String> {
+    let mut context = Context::new(program);
+
+    let (maze, cost) = explore(&mut context)?;
+    let target = match maze.iter().find(|(_position, tile)| **tile == MapTile::Target) {
+        Some((target, _tile)) => target.clone(),
+        None => return Err("The droid did not find the oxygen system.".to_string()),
+    };
+
+    // display(&maze);
+
+    Ok((maze, target, cost))
+}
+
+impl Solution for Day15 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let (maze, target, _moves) = match survey(&parse(input)?) {
+            Ok(survey) => survey,
+            Err(message) => return Ok(message),
+        };
+        let start = Position { x: 0, y: 0 };
+
+        Ok(shortest_path(&maze, &start, &target).unwrap().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let (maze, target, _moves) = match survey(&parse(input)?) {
+            Ok(survey) => survey,
+            Err(message) => return Ok(message),
+        };
+        let fill_times = flood_fill(&maze, &target);
+
+        Ok(fill_times.values().max().unwrap().to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+
+    // The oxygen spreading through the explored area, one frame per minute.
+    // Nothing to show if the droid could not survey the area.
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        let (maze, target, _moves) = match survey(&parse(input)?) {
+            Ok(survey) => survey,
+            Err(_message) => return Ok(false),
+        };
+        let fill_times = flood_fill(&maze, &target);
+
+        let duration = *fill_times.values().max().unwrap();
+        for minute in 0..=duration {
+            frame(picture(&oxygen_frame(&maze, &fill_times, minute)));
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A repair droid walking a map drawn with '#' for walls, 'D' for where it
+    // starts and 'O' for the oxygen system. It stops answering after `moves`.
+    struct Droid {
+        rows: Vec<Vec<char>>,
+        position: (usize, usize),
+        status: Option<i64>,
+        moves: usize,
+    }
+
+    impl Droid {
+        fn new(rows: &[&str], moves: usize) -> Droid {
+            let rows = rows.iter().map(|row| row.chars().collect::<Vec<char>>()).collect::<Vec<_>>();
+            let y = rows.iter().position(|row| row.contains(&'D')).unwrap();
+            let x = rows[y].iter().position(|tile| *tile == 'D').unwrap();
+
+            Droid { rows, position: (x, y), status: None, moves }
+        }
+    }
+
+    impl Machine for Droid {
+        fn feed(&mut self, input: i64) {
+            let (x, y) = self.position;
+            let (x, y) = match input {
+                1 => (x, y - 1),
+                2 => (x, y + 1),
+                3 => (x - 1, y),
+                _ => (x + 1, y),
+            };
+
+            self.status = match self.rows[y][x] {
+                '#' => Some(0),
+                'O' => Some(2),
+                _ => Some(1),
+            };
+            if self.status != Some(0) {
+                self.position = (x, y);
+            }
+        }
+
+        fn output(&mut self) -> Option<i64> {
+            if self.moves == 0 {
+                return None;
+            }
+            self.moves -= 1;
+
+            self.status.take()
+        }
+    }
+
+    // Loops around the pillars and an open area in the middle.
+    const LOOPED: [&str; 7] = [
+        "#########",
+        "#D....#.#",
+        "#.#.#...#",
+        "#.......#",
+        "#.#.#.#O#",
+        "#.......#",
+        "#########",
+    ];
+
+    #[test]
+    fn explore_looped_maze() {
+        let mut droid = Droid::new(&LOOPED, usize::MAX);
+        let (maze, moves) = explore(&mut droid).unwrap();
+
+        // every open cell is entered and left once, and every wall next to
+        // one is bumped into once
+        let tile = |x: i64, y: i64| LOOPED.get(y as usize).and_then(|row| row.chars().nth(x as usize));
+        let mut open = 0;
+        let mut walls = 0;
+        for y in 0..LOOPED.len() as i64 {
+            for x in 0..LOOPED[0].len() as i64 {
+                let position = Position { x: x - 1, y: y - 1 };
+                let expected = match tile(x, y).unwrap() {
+                    'D' => Some(MapTile::Start),
+                    'O' => Some(MapTile::Target),
+                    '.' => Some(MapTile::Floor),
+                    _ if [(0, -1), (1, 0), (0, 1), (-1, 0)].iter().any(|(dx, dy)| {
+                        matches!(tile(x + dx, y + dy), Some('.') | Some('D') | Some('O'))
+                    }) => Some(MapTile::Wall),
+                    _ => None,
+                };
+
+                match expected {
+                    Some(MapTile::Wall) => walls += 1,
+                    Some(_) => open += 1,
+                    None => {}
+                }
+                assert_eq!(maze.get(&position), expected.as_ref(), "({}, {})", x, y);
+            }
+        }
+
+        assert_eq!(maze.len(), open + walls);
+        assert_eq!(moves, walls + 2 * (open - 1));
+    }
+
+    #[test]
+    fn droid_stops() {
+        let (_maze, moves) = explore(&mut Droid::new(&LOOPED, usize::MAX)).unwrap();
+        for stop in [0, 1, 2, moves / 2, moves - 1] {
+            let mut droid = Droid::new(&LOOPED, stop);
+            assert_eq!(explore(&mut droid).err(), Some("The droid stopped before the area was explored.".to_string()));
+        }
+
+        assert_eq!(survey(&[99]).err(), Some("The droid stopped before the area was explored.".to_string()));
+        assert_eq!(Day15.part1("99").unwrap(), "The droid stopped before the area was explored.");
+    }
+
+    #[test]
+    fn flood_fill_maze() {
+        let rows = [
+            "######",
+            "#O..##",
+            "#.#..#",
+            "#...##",
+            "######",
+        ];
+        let mut maze = SparseGrid::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, tile) in row.chars().enumerate() {
+                let tile = match tile {
+                    '#' => MapTile::Wall,
+                    'O' => MapTile::Target,
+                    _ => MapTile::Floor,
+                };
+                maze.insert(Position::new(x as i64, y as i64), tile);
+            }
+        }
+
+        let fill_times = flood_fill(&maze, &Position::new(1, 1));
+        assert_eq!(fill_times.len(), 9);
+        assert_eq!(fill_times[&Position::new(1, 1)], 0);
+        assert_eq!(fill_times[&Position::new(2, 3)], 3);
+        assert_eq!(fill_times[&Position::new(3, 3)], 4);
+        assert_eq!(fill_times[&Position::new(4, 2)], 4);
+        assert_eq!(fill_times.values().max(), Some(&4));
+        assert!(!fill_times.contains_key(&Position::new(2, 2)));
+    }
+}
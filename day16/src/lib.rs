@@ -0,0 +1,90 @@
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day16;
+
+fn extract_digits(signal: &Vec<i32>, count: usize) -> String {
+    return signal.iter().take(count).map(|token|
+        std::char::from_digit(*token as u32, 10).unwrap()
+    ).collect::<String>();
+}
+
+fn pattern(in_position: usize, out_positon: usize) -> i32 {
+    let base = vec!(0, 1, 0, -1);
+    return base[in_position / out_positon % base.len()];
+}
+
+fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    let digits = parsing::first_line(input)?.digits()?;
+
+    return Ok(digits.into_iter().map(|digit| digit as i32).collect());
+}
+
+// Runs the full transformation, every output digit depending on every input
+// digit.
+fn fft(signal: &[i32], phases: usize) -> Vec<i32> {
+    let mut real_signal = signal.to_vec();
+    for _ in 0..phases {
+        let new_signal = real_signal.iter().enumerate().map(|(i, _)| {
+            real_signal.iter().enumerate().fold(0, |acc, (j, token)| {
+                acc + token * pattern(j + 1, i + 1)
+            }).abs() % 10
+        }).collect::<Vec<i32>>();
+
+        real_signal = new_signal;
+    }
+
+    real_signal
+}
+
+impl Solution for Day16 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(extract_digits(&fft(&parse(input)?, 100), 8))
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let signal = parse(input)?;
+
+        let message_offset = extract_digits(&signal, 7).parse::<usize>().unwrap();
+        let mut real_signal = std::iter::repeat(&signal)
+        .take(10000)
+        .fold(Vec::new(), |mut acc, s| {
+            acc.extend(s);
+            return acc;
+        });
+        real_signal = real_signal.into_iter().skip(message_offset).collect::<Vec<i32>>();
+
+        for phase in 0..100 {
+            // if phase % 10 == 0 {
+            //     println!("{}", extract_digits(&real_signal, 8));
+            // }
+
+            let mut new_signal = Vec::new();
+            let mut output = 0;
+            for i in 0..real_signal.len() {
+                output += real_signal[real_signal.len() - i - 1];
+                new_signal.push(output.abs() % 10);
+            }
+
+            new_signal.reverse();
+            real_signal = new_signal;
+        }
+
+        Ok(extract_digits(&real_signal, 8))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_signal| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let signal = parse(include_str!("../examples/four-phases.txt")).unwrap();
+        assert_eq!(extract_digits(&fft(&signal, 4), 8), "01029498");
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashSet;
+
+use geometry::Direction;
+use geometry::Grid;
+use geometry::Position;
+use intcode::run;
+use intcode::Context;
+use parsing::ParseError;
+use solution::Solution;
+use visualize::Frame;
+use visualize::Rgb;
+
+pub struct Day17;
+
+fn orientation(character: char) -> Direction {
+    match character {
+        '^' => Direction::Up,
+        '>' => Direction::Right,
+        'v' => Direction::Down,
+        '<' => Direction::Left,
+        _ => unreachable!(),
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum Command {
+    MoveForward,
+    TurnLeft,
+    TurnRight,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum MapTile {
+    Robot,
+    Scaffold,
+    Intersection,
+    OpenSpace,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Robot {
+    position: Position,
+    orientation: Direction,
+}
+
+impl Robot {
+    fn new(position: Position, orientation: Direction) -> Robot {
+        Robot {
+            position,
+            orientation,
+        }
+    }
+
+    fn move_forward(&mut self, pretend: bool) -> Position {
+        let new_position = self.position.step(&self.orientation);
+
+        if !pretend { // TODO: do we still need this?
+            self.position = new_position.clone();
+        }
+
+        new_position
+    }
+
+    fn turn_left(&mut self) -> Direction {
+        self.orientation = self.orientation.left_hand_side();
+        self.orientation.clone()
+    }
+
+    fn turn_right(&mut self) -> Direction {
+        self.orientation = self.orientation.right_hand_side();
+        self.orientation.clone()
+    }
+}
+
+fn palette(tile: &MapTile) -> Rgb {
+    match tile {
+        MapTile::Robot => [255, 64, 64],
+        MapTile::Scaffold => [200, 200, 200],
+        MapTile::Intersection => [255, 200, 0],
+        MapTile::OpenSpace => [16, 16, 32],
+    }
+}
+
+fn display(map: &Grid<MapTile>) {
+    let snapshot = map.render(|tile| match tile {
+        MapTile::Robot => '*',
+        MapTile::Scaffold => '#',
+        MapTile::Intersection => 'O',
+        MapTile::OpenSpace => '.',
+    });
+
+    println!("{}\n---", snapshot);
+}
+
+fn find_paths(
+    map: &Grid<MapTile>,
+    robot: &Robot,
+    target: &Position,
+) -> Vec<Command> {
+    // BFS
+
+    let target_steps: u32 = map.values().map(|tile| match tile {
+        MapTile::Scaffold => 1,
+        MapTile::Intersection => 2,
+        _ => 0,
+    }).sum();
+
+    let mut robot = robot.clone();
+    let mut path = vec!();
+
+    while robot.position != *target {
+        let mut new_robot = robot.clone();
+        new_robot.move_forward(false);
+        if map.get(&new_robot.position) == Some(&MapTile::Scaffold)
+        || map.get(&new_robot.position) == Some(&MapTile::Intersection) {
+            path.push(Command::MoveForward);
+            robot = new_robot;
+            continue;
+        }
+
+        let mut new_robot = robot.clone();
+        new_robot.turn_left();
+        new_robot.move_forward(false);
+        if map.get(&new_robot.position) == Some(&MapTile::Scaffold)
+        || map.get(&new_robot.position) == Some(&MapTile::Intersection) {
+            path.push(Command::TurnLeft);
+            path.push(Command::MoveForward);
+            robot = new_robot;
+            continue;
+        }
+
+        let mut new_robot = robot.clone();
+        new_robot.turn_right();
+        new_robot.move_forward(false);
+        if map.get(&new_robot.position) == Some(&MapTile::Scaffold)
+        || map.get(&new_robot.position) == Some(&MapTile::Intersection) {
+            path.push(Command::TurnRight);
+            path.push(Command::MoveForward);
+            robot = new_robot;
+            continue;
+        }
+    }
+
+    let steps: u32 = path.iter().map(|command| match command {
+        Command::MoveForward => 1,
+        _ => 0,
+    }).sum();
+    dbg!(steps, target_steps);
+
+    return path;
+}
+
+fn encode_path(path: &Vec<Command>) -> Vec<u8> { // TODO: this function has bugs
+    let offset = 0;
+    let length = path.len();
+    let mut encoded = Vec::new();
+    let mut i = offset;
+    while i < offset + length {
+        let mut run_length = 0;
+        while let Some(&Command::MoveForward) = path.get(i) {
+            run_length += 1;
+            i += 1;
+        }
+
+        if run_length > 0 {
+            encoded.push(run_length);
+            encoded.push(44);
+        }
+
+        match path.get(i) {
+            Some(Command::TurnLeft) => {
+                encoded.push(76);
+                encoded.push(44);
+                i += 1;
+            }
+            Some(Command::TurnRight) => {
+                encoded.push(82);
+                encoded.push(44);
+                i += 1;
+            }
+            Some(Command::MoveForward) => unreachable!(),
+            None => {
+                break;
+            }
+        }
+    }
+
+    // encoded.pop();
+    encoded
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+// Reads the camera image into a map and returns it with the robot.
+fn scan(program: &Vec<i64>) -> (Grid<MapTile>, Option<Robot>) {
+    let mut context = Context::new(program);
+
+    let mut image = String::new();
+    while let Some(ascii_code) = run(&mut context) {
+        image.push(ascii_code as u8 as char);
+    }
+
+    read_image(&image)
+}
+
+fn read_image(image: &str) -> (Grid<MapTile>, Option<Robot>) {
+    let mut robot = None;
+    let map = Grid::parse(image, |position, character| match character {
+        '#' => MapTile::Scaffold,
+        '.' => MapTile::OpenSpace,
+        _ => {
+            robot = Some(Robot::new(position.clone(), orientation(character)));
+            MapTile::Robot
+        }
+    });
+
+    (map, robot)
+}
+
+// Marks the scaffold intersections on the map and returns the sum of their
+// alignment parameters together with the length of the scaffold.
+fn calibrate(map: &mut Grid<MapTile>) -> (i64, i64) {
+    let mut alignment_sum = 0;
+    let mut scaffold_length = 1;
+    for position in map.positions() {
+        if map[&position] != MapTile::Scaffold {
+            continue;
+        }
+
+        scaffold_length += 1;
+
+        if position.neighbours().iter().all(|neighbour| map.get(neighbour) == Some(&MapTile::Scaffold)) {
+            map[&position] = MapTile::Intersection;
+            alignment_sum += position.x * position.y;
+        }
+    }
+
+    return (alignment_sum, scaffold_length);
+}
+
+// Prints the camera image, the scaffold length and the robot's path.
+pub fn inspect(program: &Vec<i64>) {
+    let (mut map, robot) = scan(program);
+    let (_, scaffold_length) = calibrate(&mut map);
+
+    display(&map);
+    println!("{}", scaffold_length);
+
+    let robot = robot.unwrap();
+    dbg!(&robot);
+    let path = find_paths(&map, &robot, &Position { x: 12, y: 30 }); // TODO: don't hard-code target
+    let encoded_path = encode_path(&path);
+    println!("{}", encoded_path.iter().map(|code| if *code < 44 {
+        code.to_string()
+    } else {
+        (*code as char).to_string()
+    }
+    ).collect::<Vec<_>>().join(""));
+}
+
+// Walks the robot along the scaffold and returns the amount of dust it
+// collected. With `echo` the robot's video feed is printed as well.
+pub fn collect_dust(program: &Vec<i64>, echo: bool) -> Option<i64> {
+    let mut context = Context::new(program);
+
+    context.program[0] = 2;
+    let program = vec![
+        65, 44, 65, 44, 66, 44, 67, 44, 67, 44, 65, 44, 67, 44, 66, 44, 67, 44, 66, 10,
+        76, 44, 52, 44, 76, 44, 52, 44, 76, 44, 54, 44, 82, 44, 49, 48, 44, 76, 44, 54, 10,
+        76, 44, 49, 50, 44, 76, 44, 54, 44, 82, 44, 49, 48, 44, 76, 44, 54, 10,
+        82, 44, 56, 44, 82, 44, 49, 48, 44, 76, 44, 54, 10,
+        110, 10
+    ]; // TODO: calculate program from path
+    for char in program {
+        context.inputs.push_back(char);
+    }
+
+    let mut dust = None;
+    while let Some(output) = run(&mut context) {
+        if output < 127 {
+            if echo {
+                print!("{}", output as u8 as char);
+            }
+        } else {
+            dust = Some(output);
+        }
+    }
+
+    return dust;
+}
+
+impl Solution for Day17 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let (mut map, _) = scan(&parse(input)?);
+
+        Ok(calibrate(&mut map).0.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(collect_dust(&parse(input)?, false).unwrap().to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+
+    // The camera image with the intersections marked.
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        let (mut map, _) = scan(&parse(input)?);
+        calibrate(&mut map);
+        frame(Frame::from_grid(&map, palette));
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(path: &[Command]) -> String {
+        let mut commands = Vec::new();
+        let mut run_length = 0;
+        for command in path {
+            let turn = match command {
+                Command::MoveForward => {
+                    run_length += 1;
+                    continue;
+                }
+                Command::TurnLeft => "L",
+                Command::TurnRight => "R",
+            };
+
+            if run_length > 0 {
+                commands.push(run_length.to_string());
+                run_length = 0;
+            }
+            commands.push(turn.to_string());
+        }
+        commands.push(run_length.to_string());
+
+        commands.join(",")
+    }
+
+    #[test]
+    fn examples() {
+        let (mut map, _) = read_image(include_str!("../examples/alignment.txt"));
+        assert_eq!(calibrate(&mut map).0, 76);
+
+        let (mut map, robot) = read_image(include_str!("../examples/path.txt"));
+        calibrate(&mut map);
+        let path = find_paths(&map, &robot.unwrap(), &Position { x: 0, y: 2 });
+        assert_eq!(describe(&path), "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2");
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use geometry::Direction;
+use geometry::Grid;
+use geometry::Position;
+use parsing::ParseError;
+use search::bfs;
+use search::dijkstra;
+use solution::Solution;
+use visualize::Frame;
+use visualize::Rgb;
+
+pub struct Day18;
+
+#[derive(Debug, Clone)]
+enum MapTile {
+    Floor,
+    Wall,
+    Explorer,
+    Key(char),
+    Door(char),
+}
+
+// A key as seen from an explorer's start or from another key: how far away it
+// is and the keys for the doors on the way, one bit per key.
+#[derive(Debug)]
+struct Route {
+    key: char,
+    position: Position,
+    distance: u32,
+    doors: u32,
+}
+
+// Where the explorers are and the keys they hold, one bit per key.
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct State {
+    explorers: Vec<Position>,
+    keys: u32,
+}
+
+fn key_bit(key: char) -> u32 {
+    1 << (key as u8 - b'a')
+}
+
+fn palette(tile: &MapTile) -> Rgb {
+    match tile {
+        MapTile::Floor => [40, 40, 48],
+        MapTile::Wall => [150, 140, 120],
+        MapTile::Explorer => [255, 64, 64],
+        MapTile::Key(_) => [255, 215, 0],
+        MapTile::Door(_) => [64, 160, 255],
+    }
+}
+
+fn display(map: &Grid<MapTile>) {
+    let snapshot = map.render(|tile| match tile {
+        MapTile::Floor => '.',
+        MapTile::Wall => '#',
+        MapTile::Explorer => '@',
+        MapTile::Key(key) => *key,
+        MapTile::Door(door) => *door,
+    });
+
+    println!("{}", snapshot);
+}
+
+fn open(map: &Grid<MapTile>, position: &Position) -> bool {
+    !matches!(map.get(position), Some(MapTile::Wall) | None)
+}
+
+// With `held` keys, doors without their key block the way and so does every
+// key not held yet, because walking over it picks it up. Without, every door
+// is open and the route lists the ones it passes.
+fn routes(map: &Grid<MapTile>, from: &Position, held: Option<u32>) -> Vec<Route> {
+    let search = bfs(from.clone(), |position| {
+        if let (MapTile::Key(key), Some(keys)) = (&map[position], held) {
+            if position != from && keys & key_bit(*key) == 0 {
+                return Vec::new();
+            }
+        }
+
+        position.neighbours().into_iter().filter(|neighbour| match (map.get(neighbour), held) {
+            (Some(MapTile::Door(door)), Some(keys)) => keys & key_bit(door.to_ascii_lowercase()) != 0,
+            _ => open(map, neighbour),
+        }).collect::<Vec<_>>()
+    }, |_position| false);
+
+    search.reached().filter_map(|(position, distance)| match map[position] {
+        MapTile::Key(key) if position != from => {
+            let doors = search.path(position).unwrap().iter().fold(0, |doors, step| match map[step] {
+                MapTile::Door(door) => doors | key_bit(door.to_ascii_lowercase()),
+                _ => doors,
+            });
+
+            Some(Route { key, position: position.clone(), distance: *distance as u32, doors })
+        }
+        _ => None,
+    }).collect()
+}
+
+// A door is on a loop if its neighbours still reach each other with the door
+// walled up.
+fn door_on_loop(map: &Grid<MapTile>) -> bool {
+    map.iter().any(|(door, tile)| {
+        if !matches!(tile, MapTile::Door(_)) {
+            return false;
+        }
+
+        let mut sides = door.neighbours().into_iter().filter(|side| open(map, side));
+        let first = match sides.next() {
+            Some(first) => first,
+            None => return false,
+        };
+        let search = bfs(first, |position| {
+            position.neighbours().into_iter().filter(|neighbour| *neighbour != door && open(map, neighbour)).collect::<Vec<_>>()
+        }, |_position| false);
+
+        sides.any(|side| search.cost(&side).is_some())
+    })
+}
+
+// Every step of the search walks one explorer to a key it can reach, until
+// all keys are held. If no door is on a loop, the doors between two points
+// are the same on every path, so the routes are searched once per position.
+// Otherwise a loop may lead around a door, and the routes are searched again
+// for every set of keys held.
+fn collect_keys(map: &Grid<MapTile>, explorers: &[Position]) -> Option<u32> {
+    let all_keys = map.values().fold(0, |keys, tile| match tile {
+        MapTile::Key(key) => keys | key_bit(*key),
+        _ => keys,
+    });
+    let per_keys = door_on_loop(map);
+
+    let mut routes_from = HashMap::new();
+    let start = State { explorers: explorers.to_vec(), keys: 0 };
+    let search = dijkstra(start, |state| {
+        let held = if per_keys { Some(state.keys) } else { None };
+
+        let mut successors = Vec::new();
+        for (i, explorer) in state.explorers.iter().enumerate() {
+            let routes = routes_from.entry((explorer.clone(), held)).or_insert_with(|| routes(map, explorer, held));
+            for route in routes.iter() {
+                if state.keys & key_bit(route.key) != 0 || route.doors & !state.keys != 0 {
+                    continue;
+                }
+
+                let mut explorers = state.explorers.clone();
+                explorers[i] = route.position.clone();
+
+                successors.push((State { explorers, keys: state.keys | key_bit(route.key) }, route.distance));
+            }
+        }
+
+        successors
+    }, |state| state.keys == all_keys);
+
+    // dbg!(&search.statistics);
+    search.goal_cost()
+}
+
+fn answer(steps: Option<u32>) -> String {
+    match steps {
+        Some(steps) => steps.to_string(),
+        None => "Not all keys can be collected.".to_string(),
+    }
+}
+
+fn parse(input: &str) -> Result<(Grid<MapTile>, Vec<Position>), ParseError> {
+    let mut explorers = Vec::new();
+    let map = Grid::try_parse(input, |position, character| match character {
+        '.' => Some(MapTile::Floor),
+        '#' => Some(MapTile::Wall),
+        '@' => {
+            explorers.push(position.clone());
+            Some(MapTile::Explorer)
+        },
+        _ if character.is_ascii_lowercase() => Some(MapTile::Key(character)),
+        _ if character.is_ascii_uppercase() => Some(MapTile::Door(character)),
+        _ => None,
+    })?;
+
+    if explorers.is_empty() {
+        return Err(ParseError::new(1, 1, "the map has no entrance"));
+    }
+
+    return Ok((map, explorers));
+}
+
+impl Solution for Day18 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let (map, explorers) = parse(input)?;
+
+        // display(&map);
+        Ok(answer(collect_keys(&map, &explorers)))
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let (mut map, explorers) = parse(input)?;
+
+        // the entrance gets walled in, so there has to be room around it
+        if explorers.len() != 1 || !explorers[0].neighbours_diagonal().iter().all(|position| map.contains(position)) {
+            return Err(ParseError::new(1, 1, "expected a single entrance away from the edge of the map"));
+        }
+
+        let new_walls = vec!(
+            explorers[0].clone(),
+            explorers[0].step(&Direction::Up),
+            explorers[0].step(&Direction::Right),
+            explorers[0].step(&Direction::Down),
+            explorers[0].step(&Direction::Left),
+        );
+
+        for wall in new_walls {
+            map[&wall] = MapTile::Wall;
+        }
+
+        let new_explorers = vec!(
+            explorers[0].step(&Direction::Up).step(&Direction::Left),
+            explorers[0].step(&Direction::Up).step(&Direction::Right),
+            explorers[0].step(&Direction::Down).step(&Direction::Left),
+            explorers[0].step(&Direction::Down).step(&Direction::Right),
+        );
+
+        for explorer in new_explorers.clone() {
+            map[&explorer] = MapTile::Explorer;
+        }
+
+        // display(&map);
+        Ok(answer(collect_keys(&map, &new_explorers)))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_map| ())
+    }
+
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        let (map, _) = parse(input)?;
+        frame(Frame::from_grid(&map, palette));
+
+        Ok(true)
+    }
+}
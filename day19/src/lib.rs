@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+
+use intcode::driver::exchange;
+use intcode::Context;
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day19;
+
+fn inside_beam(program: &[i64], x: i64, y: i64) -> bool {
+    match exchange(&mut Context::new(program), &[x, y], 1) {
+        Some(outputs) => {
+            outputs[0] == 1
+        }
+        None => {
+            unreachable!();
+        }
+    }
+}
+
+pub struct Beam {
+    program: Vec<i64>,
+    pub probes: usize,
+    starts: BTreeMap<i64, Option<i64>>,
+    ends: BTreeMap<i64, Option<i64>>,
+}
+
+impl Beam {
+    pub fn new(program: Vec<i64>) -> Beam {
+        Beam {
+            program,
+            probes: 0,
+            starts: BTreeMap::new(),
+            ends: BTreeMap::new(),
+        }
+    }
+
+    fn probe(&mut self, x: i64, y: i64) -> bool {
+        if x < 0 {
+            return false;
+        }
+
+        self.probes += 1;
+        inside_beam(&self.program, x, y)
+    }
+
+    // Scales the edges of the closest row already traced, so a row far away
+    // from every known one can still be found with a short search. Only rows
+    // with both edges known count, as start() records a start on its own.
+    fn estimate(&self, y: i64) -> (i64, i64) {
+        let known = |(row, start): (&i64, &Option<i64>)| {
+            let end = self.ends.get(row).copied().flatten()?;
+            start.map(|start| (*row, start, end))
+        };
+        let below = self.starts.range(..y).rev().find_map(known);
+        let above = self.starts.range(y..).find_map(known);
+
+        let closest = match (below, above) {
+            (Some(below), Some(above)) => if y - below.0 <= above.0 - y { Some(below) } else { Some(above) },
+            (below, above) => below.or(above),
+        };
+
+        match closest {
+            Some((row, start, end)) => {
+                if row == 0 {
+                    (start, end)
+                } else {
+                    (start * y / row, end * y / row)
+                }
+            }
+            None => (0, 0),
+        }
+    }
+
+    fn trace(&mut self, y: i64) -> Option<(i64, i64)> {
+        let (guess_start, guess_end) = self.estimate(y);
+
+        let mut start = None;
+        for distance in 0..=(y + 1) {
+            if self.probe(guess_start + distance, y) {
+                start = Some(guess_start + distance);
+                break;
+            }
+            if distance > 0 && self.probe(guess_start - distance, y) {
+                start = Some(guess_start - distance);
+                break;
+            }
+        }
+
+        let edges = match start {
+            Some(mut start) => {
+                while self.probe(start - 1, y) {
+                    start -= 1;
+                }
+
+                let mut end = std::cmp::max(start, guess_end);
+                if self.probe(end, y) {
+                    while self.probe(end + 1, y) {
+                        end += 1;
+                    }
+                } else {
+                    end -= 1;
+                    while !self.probe(end, y) {
+                        end -= 1;
+                    }
+                }
+
+                Some((start, end))
+            }
+            None => None,
+        };
+
+        self.starts.insert(y, edges.map(|edges| edges.0));
+        self.ends.insert(y, edges.map(|edges| edges.1));
+        edges
+    }
+
+    // Both edges only ever move right, so a row directly below a known one
+    // usually costs a single probe per edge.
+    fn start(&mut self, y: i64) -> Option<i64> {
+        if let Some(start) = self.starts.get(&y) {
+            return *start;
+        }
+
+        if let Some(Some(previous)) = self.starts.get(&(y - 1)).cloned() {
+            // an unknown end would leave the scan unbounded on an empty row
+            let previous_end = self.end(y - 1).unwrap_or(previous);
+            let mut x = previous;
+            while x <= previous_end + 1 {
+                if self.probe(x, y) {
+                    self.starts.insert(y, Some(x));
+                    return Some(x);
+                }
+                x += 1;
+            }
+        }
+
+        self.trace(y).map(|edges| edges.0)
+    }
+
+    fn end(&mut self, y: i64) -> Option<i64> {
+        if let Some(end) = self.ends.get(&y) {
+            return *end;
+        }
+
+        if let Some(Some(previous)) = self.ends.get(&(y - 1)).cloned() {
+            let mut x = previous;
+            if self.probe(x + 1, y) {
+                x += 1;
+                while self.probe(x + 1, y) {
+                    x += 1;
+                }
+                self.ends.insert(y, Some(x));
+                return Some(x);
+            }
+            if self.probe(x, y) {
+                self.ends.insert(y, Some(x));
+                return Some(x);
+            }
+        }
+
+        self.trace(y).map(|edges| edges.1)
+    }
+
+    pub fn count(&mut self, width: i64, height: i64) -> i64 {
+        let mut num_pulled = 0;
+        for y in 0..height {
+            if let (Some(start), Some(end)) = (self.start(y), self.end(y)) {
+                let end = std::cmp::min(end, width - 1);
+                if end >= start {
+                    num_pulled += end - start + 1;
+                }
+            }
+        }
+
+        num_pulled
+    }
+
+    // The ship fits with its bottom-left corner on the start of row `bottom`
+    // if its top-right corner is still inside the beam.
+    fn fits(&mut self, bottom: i64, size: i64) -> Option<(i64, i64)> {
+        let top = bottom - size + 1;
+        let x = self.start(bottom)?;
+
+        if self.probe(x + size - 1, top) {
+            Some((x, top))
+        } else {
+            None
+        }
+    }
+
+    pub fn closest_ship(&mut self, size: i64) -> (i64, i64) {
+        let mut bottom = size - 1;
+        loop {
+            if let Some(ship) = self.fits(bottom, size) {
+                return ship;
+            }
+            bottom += 1;
+        }
+    }
+
+    pub fn closest_ship_bisect(&mut self, size: i64) -> (i64, i64) {
+        if let Some(ship) = self.fits(size - 1, size) {
+            return ship;
+        }
+
+        let mut low = size - 1;
+        let mut high = 2 * size;
+        while self.fits(high, size).is_none() {
+            low = high;
+            high *= 2;
+        }
+
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            if self.fits(middle, size).is_some() {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+
+        // the beam edges are jagged, so rows up to a ship's height above the
+        // bisection result may fit as well, even past some that do not
+        let mut row = high - 1;
+        while row >= size - 1 && row >= high - size {
+            if self.fits(row, size).is_some() {
+                high = row;
+            }
+            row -= 1;
+        }
+
+        self.fits(high, size).unwrap()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+impl Solution for Day19 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let mut beam = Beam::new(parse(input)?);
+        Ok(beam.count(50, 50).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let mut beam = Beam::new(parse(input)?);
+        let (x, y) = beam.closest_ship(100);
+
+        Ok((x * 10000 + y).to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An Intcode program pulling (x, y) if x / y lies between the two slopes
+    // given as (numerator, denominator). With `wobble` set, the right edge
+    // moves one to the left on odd rows.
+    fn synthetic_beam(left: (i64, i64), right: (i64, i64), wobble: bool) -> Vec<i64> {
+        vec![
+            3, 100,
+            3, 101,
+            1002, 100, left.1, 102,
+            1002, 101, left.0, 103,
+            7, 102, 103, 106,
+            // y % 2 by repeated subtraction
+            1001, 101, 0, 104,
+            1007, 104, 2, 105,
+            1005, 105, 35,
+            1001, 104, -2, 104,
+            1105, 1, 20,
+            0,
+            1002, 104, wobble as i64, 104,
+            1, 100, 104, 102,
+            1002, 102, right.1, 102,
+            1002, 101, right.0, 103,
+            7, 103, 102, 107,
+            1, 106, 107, 106,
+            1008, 106, 0, 106,
+            4, 106,
+            99,
+        ]
+    }
+
+    fn pulled(left: (i64, i64), right: (i64, i64), wobble: bool, x: i64, y: i64) -> bool {
+        let shift = if wobble { y % 2 } else { 0 };
+        x * left.1 >= y * left.0 && (x + shift) * right.1 <= y * right.0
+    }
+
+    // The first row from the top whose beam start leaves room for the ship.
+    fn brute_force(left: (i64, i64), right: (i64, i64), wobble: bool, size: i64) -> (i64, i64) {
+        (size - 1..).find_map(|bottom| {
+            let x = (0..=bottom).find(|x| pulled(left, right, wobble, *x, bottom))?;
+            let top = bottom - size + 1;
+            if pulled(left, right, wobble, x + size - 1, top) { Some((x, top)) } else { None }
+        }).unwrap()
+    }
+
+    #[test]
+    fn synthetic_beams() {
+        for wobble in [false, true] {
+            let program = synthetic_beam((3, 4), (9, 10), wobble);
+            for y in 0..40 {
+                for x in 0..40 {
+                    assert_eq!(inside_beam(&program, x, y), pulled((3, 4), (9, 10), wobble, x, y), "({}, {})", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count() {
+        for (left, right, wobble) in [((5, 7), (6, 5), false), ((3, 4), (9, 10), true)] {
+            let expected = (0..50).map(|y| (0..50).filter(|x| pulled(left, right, wobble, *x, y)).count() as i64).sum::<i64>();
+            assert_eq!(Beam::new(synthetic_beam(left, right, wobble)).count(50, 50), expected);
+        }
+    }
+
+    #[test]
+    fn strategies_agree() {
+        for (left, right, wobble) in [((5, 7), (6, 5), false), ((3, 4), (9, 10), false), ((3, 4), (9, 10), true)] {
+            let program = synthetic_beam(left, right, wobble);
+
+            for size in [2, 3, 5, 10, 20, 50] {
+                let linear = Beam::new(program.clone()).closest_ship(size);
+                let bisect = Beam::new(program.clone()).closest_ship_bisect(size);
+
+                assert_eq!(linear, brute_force(left, right, wobble, size), "linear, size {}", size);
+                assert_eq!(bisect, linear, "bisect, size {}", size);
+            }
+        }
+    }
+}
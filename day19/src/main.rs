@@ -1,222 +1,40 @@
-use std::collections::HashMap;
-use std::collections::VecDeque;
+use std::io::Read;
 
-#[derive(Debug)]
-struct Context {
-    program: Vec<i64>,
-    pc: usize,
-    inputs: VecDeque<i64>,
-    relative_base: usize,
-    heap: HashMap<usize, i64>,
-}
-
-fn read_helper(context: &mut Context, address: usize) -> i64 {
-    if address >= context.program.len() {
-        match context.heap.get(&address) {
-            Some(value) => {
-                return *value;
-            }
-            None => {
-                context.heap.insert(address, 0);
-                return 0;
-            }
-        }
-    } else {
-        return context.program[address];
-    }
-}
-
-fn write_helper(context: &mut Context, address: usize, value: i64) {
-    if address >= context.program.len() {
-        context.heap.insert(address, value);
-    } else {
-        context.program[address] = value;
-    }
-}
-
-fn read(context: &mut Context, offset: u32) -> i64 {
-    let mode = context.program[context.pc] / (10i64.pow(offset + 1)) % 10;
-    // dbg!(mode);
-
-    match mode {
-        0 => {
-            let address = context.program[context.pc + offset as usize] as usize;
-            return read_helper(context, address);
-        }
-        1 => {
-            return context.program[context.pc + offset as usize];
-        }
-        2 => {
-            let address = context.program[context.pc + offset as usize];
-            return read_helper(context, (context.relative_base as i64 + address) as usize);
-        }
-       _ => panic!("Illegal mode flag."),
-    }
-}
-
-fn write(context: &mut Context, offset: u32, value: i64) {
-    let mode = context.program[context.pc] / (10i64.pow(offset + 1)) % 10;
-
-    match mode {
-        0 => {
-            let address = context.program[context.pc + offset as usize] as usize;
-            write_helper(context, address, value);
-        }
-        2 => {
-            let address = context.program[context.pc + offset as usize];
-            write_helper(context, (context.relative_base as i64 + address) as usize, value);
-        }
-       _ => panic!("Illegal mode flag."),
-    }
-}
-
-fn run(mut context: &mut Context) -> Option<i64> {
-    while context.program[context.pc] != 99 {
-        // println!("{}: {}", context.pc, context.program[context.pc]);
-        match context.program[context.pc] % 100 {
-            1 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-                write(&mut context, 3, operand1 + operand2);
-
-                context.pc += 4;
-            }
-            2 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-                write(&mut context, 3, operand1 * operand2);
-
-                context.pc += 4;
-            }
-            3 => {
-                let input = match context.inputs.pop_front() {
-                    Some(value) => value,
-                    None => unreachable!(),
-                };
-
-                write(&mut context, 1, input);
+use day19::Beam;
+use solution::or_exit;
 
-                context.pc += 2;
-            }
-            4 => {
-                let operand = read(&mut context, 1);
-
-                context.pc += 2;
-                return Some(operand);
-            }
-            5 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 != 0 {
-                    context.pc = operand2 as usize;
-                } else {
-                    context.pc += 3;
-                }
-            }
-            6 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 == 0 {
-                    context.pc = operand2 as usize;
-                } else {
-                    context.pc += 3;
-                }
-            }
-            7 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 < operand2 {
-                    write(&mut context, 3, 1);
-                } else {
-                    write(&mut context, 3, 0);
-                }
-                context.pc += 4;
-            }
-            8 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 == operand2 {
-                    write(&mut context, 3, 1);
-                } else {
-                    write(&mut context, 3, 0);
-                }
-                context.pc += 4;
-            }
-            9 => {
-                let operand = read(&mut context, 1);
-
-                context.relative_base = (context.relative_base as i64 + operand) as usize;
-                context.pc += 2;
-            }
-            _ => panic!("Illegal operation."),
-        }
-    }
-
-    return None;
-}
-
-fn inside_beam(program: Vec<i64>, x: i64, y: i64) -> bool {
-    let mut context = Context {
-        program,
-        pc: 0,
-        inputs: VecDeque::new(),
-        relative_base: 0,
-        heap: HashMap::new(),
-    };
-    context.inputs.push_back(x);
-    context.inputs.push_back(y);
-
-    let pulled = match run(&mut context) {
-        Some(output) => {
-            output
-        }
-        None => {
-            unreachable!();
-        }
-    };
-
-    return pulled == 1;
+fn usage() -> ! {
+    eprintln!("usage: day19 [--size <size>] [--bisect] < <program>");
+    std::process::exit(1);
 }
 
 fn main() {
     let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input = input.trim_end().to_string();
-    let mut program = Vec::new();
-    for token in input.split(",") {
-        program.push(token.parse::<i64>().expect(token));
-    }
+    std::io::stdin().read_to_string(&mut input).unwrap();
 
-    let mut num_pulled = 0;
-    for y in 0..50 {
-        for x in 0..50 {
-            if inside_beam(program.clone(), x, y) {
-                num_pulled += 1;
-            }
-        }
-    }
+    let program = or_exit(day19::parse(&input));
 
-    println!("{}", num_pulled);
+    let mut beam = Beam::new(program.clone());
+    println!("{}", beam.count(50, 50));
+    eprintln!("{} probes", beam.probes);
 
-    let mut x = 0;
-    let mut y = 0;
-    loop {
-        if !inside_beam(program.clone(), x + 99, y) {
-            y += 1;
-            continue;
-        }
-
-        if !inside_beam(program.clone(), x, y + 99) {
-            x += 1;
-            continue;
-        }
+    let args = std::env::args().collect::<Vec<String>>();
+    let size = match args.iter().position(|arg| arg == "--size") {
+        Some(index) => match args.get(index + 1).and_then(|size| size.parse::<i64>().ok()) {
+            Some(size) if size > 0 => size,
+            _ => usage(),
+        },
+        None => 100,
+    };
+    let bisect = args.iter().any(|arg| arg == "--bisect");
 
-        break;
-    }
+    let mut beam = Beam::new(program);
+    let (x, y) = if bisect {
+        beam.closest_ship_bisect(size)
+    } else {
+        beam.closest_ship(size)
+    };
 
     println!("{}", x * 10000 + y);
+    eprintln!("{} probes", beam.probes);
 }
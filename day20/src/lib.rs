@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use geometry::Direction;
+use geometry::Grid;
+use geometry::Position;
+use parsing::ParseError;
+use search::bfs;
+use search::Search;
+use solution::Solution;
+use visualize::Frame;
+use visualize::Rgb;
+
+pub struct Day20;
+
+#[derive(Debug, Clone)]
+enum MapTile {
+    Floor,
+    Wall,
+    Portal,
+    Space(Option<char>),
+}
+
+#[derive(Debug)]
+struct KeyLocation {
+    position: Position,
+    distance: u32,
+}
+
+fn palette(tile: &MapTile) -> Rgb {
+    match tile {
+        MapTile::Floor => [200, 200, 200],
+        MapTile::Wall => [48, 48, 64],
+        MapTile::Portal => [160, 64, 255],
+        MapTile::Space(Some(_)) => [96, 96, 96],
+        MapTile::Space(None) => [0, 0, 0],
+    }
+}
+
+fn display(map: &Grid<MapTile>) {
+    let snapshot = map.render(|tile| match tile {
+        MapTile::Floor => '.',
+        MapTile::Wall => '#',
+        MapTile::Portal => '*',
+        MapTile::Space(Some(label)) => *label,
+        MapTile::Space(None) => ' ',
+    });
+
+    println!("{}", snapshot);
+}
+
+fn identify_portals(
+    map: Grid<MapTile>
+) -> Result<(Grid<MapTile>, Position, Position, HashMap<Position, (Position, bool)>), ParseError> {
+    let mut portals_by_name = HashMap::new();
+
+    let max_x = map.width() as i64 - 1;
+    let max_y = map.height() as i64 - 1;
+
+    let new_map = map.map(|position, tile| {
+        let mut new_tile = tile.clone();
+        if let MapTile::Floor = tile {
+            for direction in Direction::all() {
+                let stepped = position.step(&direction);
+                if let Some(&MapTile::Space(Some(label1))) = map.get(&stepped) {
+                    new_tile = MapTile::Portal;
+                    let stepped = stepped.step(&direction);
+                    if let Some(&MapTile::Space(Some(label2))) = map.get(&stepped) {
+                        let mut name = String::new();
+                        if label1 < label2 {
+                            name.push(label1);
+                            name.push(label2);
+                        } else {
+                            name.push(label2);
+                            name.push(label1);
+                        }
+                        if stepped.x == 0 || stepped.x == max_x || stepped.y == 0 || stepped.y == max_y {
+                            portals_by_name.entry(name).or_insert(Vec::new()).push((position.clone(), false));
+                        } else {
+                            portals_by_name.entry(name).or_insert(Vec::new()).push((position.clone(), true));
+                        }
+                    }
+                }
+            }
+        }
+
+        new_tile
+    });
+
+    let mut portals = HashMap::new();
+    for (_name, endpoints) in portals_by_name.clone() {
+        if endpoints.len() == 2 {
+            portals.insert(endpoints[0].0.clone(), endpoints[1].clone());
+            portals.insert(endpoints[1].0.clone(), endpoints[0].clone());
+        }
+    }
+
+    // dbg!(&portals);
+
+    let endpoint = |name: &str| match portals_by_name.get(name) {
+        Some(endpoints) => Ok(endpoints[0].0.clone()),
+        None => Err(ParseError::new(1, 1, &format!("the maze has no {} portal", name))),
+    };
+
+    return Ok((new_map, endpoint("AA")?, endpoint("ZZ")?, portals));
+}
+
+fn can_visit(
+    map: &Grid<MapTile>,
+    position: &Position
+) -> bool {
+    match map.get(position) {
+        Some(MapTile::Floor) => true,
+        Some(MapTile::Wall) => false,
+        Some(MapTile::Portal) => true,
+        Some(MapTile::Space(_)) => false,
+        None => false,
+    }
+}
+
+fn shortest_path(
+    map: &Grid<MapTile>,
+    start: &Position,
+    target: &Position,
+    portals: &HashMap<Position, (Position, bool)>
+) -> Search<Position, usize> {
+    bfs(start.clone(), |position| {
+        let mut next = position.neighbours();
+        if let Some((destination, _inner)) = portals.get(position) {
+            next.push(destination.clone());
+        }
+
+        next.into_iter().filter(|next| can_visit(map, next)).collect::<Vec<_>>()
+    }, |position| position == target)
+}
+
+// Portals on the inner edge lead one level down, those on the outer edge one
+// level up. The outermost level has no way further up.
+fn shortest_path_recursive(
+    map: &Grid<MapTile>,
+    start: &Position,
+    target: &Position,
+    portals: &HashMap<Position, (Position, bool)>
+) -> Option<usize> {
+    let search = bfs((start.clone(), 0), |(position, level)| {
+        let mut next = position.neighbours().into_iter().map(|neighbour| (neighbour, *level)).collect::<Vec<_>>();
+        if let Some((destination, inner)) = portals.get(position) {
+            let level = if *inner { level - 1 } else { level + 1 };
+            next.push((destination.clone(), level));
+        }
+
+        next.into_iter().filter(|(next, level)| *level >= 0 && can_visit(map, next)).collect::<Vec<_>>()
+    }, |(position, level)| position == target && *level == 0);
+
+    search.goal_cost()
+}
+
+fn parse(input: &str) -> Result<Grid<MapTile>, ParseError> {
+    let map = Grid::try_parse(input, |_position, character| match character {
+        '.' => Some(MapTile::Floor),
+        '#' => Some(MapTile::Wall),
+        ' ' => Some(MapTile::Space(None)),
+        label if label.is_ascii_uppercase() => Some(MapTile::Space(Some(label))),
+        _ => None,
+    })?;
+
+    // display(&map);
+    return Ok(map);
+}
+
+impl Solution for Day20 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let (map, start, target, portals) = identify_portals(parse(input)?)?;
+        match shortest_path(&map, &start, &target, &portals).goal_cost() {
+            Some(distance) => { Ok(distance.to_string()) }
+            None => { Ok("No path.".to_string()) }
+        }
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let (map, start, target, portals) = identify_portals(parse(input)?)?;
+        match shortest_path_recursive(&map, &start, &target, &portals) {
+            Some(distance) => { Ok(distance.to_string()) }
+            None => { Ok("No path.".to_string()) }
+        }
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        identify_portals(parse(input)?).map(|_maze| ())
+    }
+
+    // The maze with the shortest path of part 1 drawn over it.
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        let (map, start, target, portals) = identify_portals(parse(input)?)?;
+
+        let mut picture = Frame::from_grid(&map, palette);
+        for position in shortest_path(&map, &start, &target, &portals).goal_path().unwrap_or_default() {
+            picture.set(&position, [255, 160, 0]);
+        }
+        frame(picture);
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,114 @@
+pub mod springdroid;
+pub mod springscript;
+
+use intcode::run;
+use intcode::Context;
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day21;
+
+fn string2ints(string: &str) -> Vec<i64> {
+    string.as_bytes().iter().map(|byte| *byte as i64).collect::<Vec<_>>()
+}
+
+fn ints2string(ints: &Vec<i64>) -> String {
+    let bytes = ints.iter().map(|int| *int as u8).collect::<Vec<_>>();
+    match String::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(_) => String::new(),
+    }
+}
+
+// Feeds the springscript to the droid and returns the hull damage it reports,
+// or the text it printed when it did not make it across.
+fn execute_springscript(program: &Vec<i64>, springscript: &Vec<String>) -> Result<i64, String> {
+    let mut context = Context::new(program);
+
+    for input in string2ints(&(springscript.join("\n") + "\n")) {
+        context.inputs.push_back(input);
+    }
+
+    let mut outputs = Vec::new();
+    loop {
+        match run(&mut context) {
+            Some(output) => {
+                outputs.push(output);
+            }
+            None => {
+                break;
+            }
+        };
+    }
+
+    match outputs.last() {
+        Some(last) if *last > 255 => Ok(*last),
+        _ => Err(ints2string(&outputs)),
+    }
+}
+
+// A springscript that got the droid across, how many hulls it took to find
+// it and the hull damage the droid reported.
+pub struct Discovery {
+    pub springscript: Vec<String>,
+    pub hulls: usize,
+    pub damage: i64,
+}
+
+// Starts out knowing no hull at all and keeps adding the hulls the droid
+// falls into until the search comes up with a springscript that survives.
+pub fn search_springscript(program: &Vec<i64>, mode: springdroid::Mode) -> Result<Discovery, String> {
+    let mut hulls = Vec::new();
+    loop {
+        let code = match springdroid::search(&hulls, mode) {
+            Some(code) => code,
+            None => return Err(format!("No springscript survives all {} known hulls.", hulls.len())),
+        };
+        for hull in &hulls {
+            if let Some(position) = springdroid::simulate(&code, hull, mode) {
+                return Err(format!("The springscript found for {} known hulls falls into the hole at {}.", hulls.len(), position));
+            }
+        }
+
+        let mut springscript = code.iter().map(|instruction| instruction.to_string()).collect::<Vec<String>>();
+        springscript.push(mode.command().to_string());
+
+        match execute_springscript(program, &springscript) {
+            Ok(damage) => return Ok(Discovery { springscript, hulls: hulls.len(), damage }),
+            Err(output) => {
+                match springdroid::parse_hull(&output) {
+                    Some(hull) => hulls.push(hull),
+                    None => return Err(output),
+                }
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+fn hull_damage(program: &Vec<i64>, expression: &str, mode: springdroid::Mode) -> String {
+    let mut springscript = springscript::compile(expression).unwrap();
+    springscript.push(mode.command().to_string());
+
+    match execute_springscript(program, &springscript) {
+        Ok(damage) => damage.to_string(),
+        Err(output) => output,
+    }
+}
+
+impl Solution for Day21 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(hull_damage(&parse(input)?, "(!C & D) | !A", springdroid::Mode::Walk))
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(hull_damage(&parse(input)?, "(!C & D & H) | (!B & D & H) | !A", springdroid::Mode::Run))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
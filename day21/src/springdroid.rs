@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use crate::springscript::compile_table;
+use crate::springscript::parse_instruction;
+use crate::springscript::Instruction;
+use crate::springscript::Operation;
+
+// Attempts at compiling a complete decision table before the search gives up.
+const MAX_ATTEMPTS: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    pub fn range(&self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+
+    pub fn command(&self) -> &'static str {
+        match self {
+            Mode::Walk => "WALK",
+            Mode::Run => "RUN",
+        }
+    }
+
+    fn sensors(&self) -> Vec<char> {
+        ('A'..='I').take(self.range()).collect()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Outcome {
+    Survived,
+    Fell(usize),
+    Undecided(u32),
+}
+
+pub fn validate(code: &Vec<Instruction>, mode: Mode) -> Result<(), String> {
+    let sensors = mode.sensors();
+    for instruction in code {
+        if instruction.source != 'T' && instruction.source != 'J' && !sensors.contains(&instruction.source) {
+            return Err(format!("Sensor {} is not available in {} mode.", instruction.source, mode.command()));
+        }
+    }
+
+    Ok(())
+}
+
+// A springscript as the droid reads it, with WALK or RUN on its last line.
+pub fn parse_springscript(script: &str) -> Result<(Vec<Instruction>, Mode), String> {
+    let lines = script.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).collect::<Vec<&str>>();
+    let mode = match lines.last() {
+        Some(&"WALK") => Mode::Walk,
+        Some(&"RUN") => Mode::Run,
+        _ => return Err("Springscript must end with WALK or RUN.".to_string()),
+    };
+
+    let code = lines[..lines.len() - 1].iter().map(|line| parse_instruction(line)).collect::<Result<Vec<Instruction>, String>>()?;
+    validate(&code, mode)?;
+
+    Ok((code, mode))
+}
+
+// Bit `n` of `sensors` is set if there is ground `n + 1` tiles ahead.
+pub fn execute(code: &[Instruction], sensors: u32) -> bool {
+    let mut t = false;
+    let mut j = false;
+    for instruction in code {
+        let source = match instruction.source {
+            'T' => t,
+            'J' => j,
+            sensor => sensors & (1 << (sensor as u8 - b'A')) != 0,
+        };
+
+        let target = match instruction.target {
+            'T' => &mut t,
+            _ => &mut j,
+        };
+
+        *target = match instruction.operation {
+            Operation::And => source && *target,
+            Operation::Or => source || *target,
+            Operation::Not => !source,
+        };
+    }
+
+    j
+}
+
+// Everything beyond the known part of the hull counts as ground.
+fn sensors(hull: &[bool], position: usize, range: usize) -> u32 {
+    (0..range).filter(|offset| {
+        *hull.get(position + offset + 1).unwrap_or(&true)
+    }).fold(0, |sensors, offset| sensors | (1 << offset))
+}
+
+fn walk<F>(hull: &[bool], range: usize, mut decide: F) -> Outcome where F: FnMut(u32) -> Option<bool> {
+    let last_hole = match hull.iter().rposition(|ground| !ground) {
+        Some(last_hole) => last_hole,
+        None => return Outcome::Survived,
+    };
+
+    let mut position = 0;
+    while position <= last_hole {
+        let sensors = sensors(hull, position, range);
+        position += match decide(sensors) {
+            Some(true) => 4,
+            Some(false) => 1,
+            None => return Outcome::Undecided(sensors),
+        };
+
+        if !*hull.get(position).unwrap_or(&true) {
+            return Outcome::Fell(position);
+        }
+    }
+
+    Outcome::Survived
+}
+
+// Returns where the droid falls into a hole, if it does.
+pub fn simulate(code: &[Instruction], hull: &[bool], mode: Mode) -> Option<usize> {
+    match walk(hull, mode.range(), |sensors| Some(execute(code, sensors))) {
+        Outcome::Fell(position) => Some(position),
+        _ => None,
+    }
+}
+
+// Picks the first hull line of the death animation, that is the first line
+// made of ground and holes only.
+pub fn parse_hull(output: &str) -> Option<Vec<bool>> {
+    let animation = &output[output.find("Didn't make it across:")?..];
+
+    animation.lines().find(|line| {
+        line.contains('#') && line.chars().all(|tile| tile == '#' || tile == '.')
+    }).map(|line| line.chars().map(|tile| tile == '#').collect())
+}
+
+fn solve(hulls: &Vec<Vec<bool>>, mode: Mode, table: &mut BTreeMap<u32, bool>, attempts: &mut usize) -> Option<Vec<Instruction>> {
+    for hull in hulls {
+        match walk(hull, mode.range(), |sensors| table.get(&sensors).cloned()) {
+            Outcome::Survived => {}
+            Outcome::Fell(_) => return None,
+            Outcome::Undecided(sensors) => {
+                for jump in [false, true] {
+                    table.insert(sensors, jump);
+                    if let Some(code) = solve(hulls, mode, table, attempts) {
+                        return Some(code);
+                    }
+                    table.remove(&sensors);
+
+                    if *attempts >= MAX_ATTEMPTS {
+                        return None;
+                    }
+                }
+
+                return None;
+            }
+        }
+    }
+
+    *attempts += 1;
+    let ones = table.iter().filter(|(_, jump)| **jump).map(|(sensors, _)| *sensors).collect::<Vec<u32>>();
+    let zeros = table.iter().filter(|(_, jump)| !**jump).map(|(sensors, _)| *sensors).collect::<Vec<u32>>();
+
+    compile_table(&mode.sensors(), &ones, &zeros).ok()
+}
+
+// Backtracks over the decision for every sensor reading the droid runs into
+// until all known hulls are crossed and the decisions compile.
+pub fn search(hulls: &Vec<Vec<bool>>, mode: Mode) -> Option<Vec<Instruction>> {
+    solve(hulls, mode, &mut BTreeMap::new(), &mut 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hull(tiles: &str) -> Vec<bool> {
+        tiles.chars().map(|tile| tile == '#').collect()
+    }
+
+    fn compile(source: &str) -> Vec<Instruction> {
+        crate::springscript::compile_expression(&crate::springscript::parse(source).unwrap()).unwrap()
+    }
+
+    const WALKABLE: [&str; 4] = ["#####.###########", "#####..#.########", "#####...#########", "#####.#..########"];
+    const RUNNABLE: [&str; 4] = ["#####.#.##..#####", "#####.##.##.#.###", "#####.#.#..##.###", "#####.#.##.##.###"];
+
+    #[test]
+    fn known_scripts() {
+        let walk = compile("(!C & D) | !A");
+        for tiles in WALKABLE {
+            assert_eq!(simulate(&walk, &hull(tiles), Mode::Walk), None, "{}", tiles);
+        }
+        assert_eq!(simulate(&walk, &hull("#####.#.##..#####"), Mode::Walk), Some(10));
+        assert_eq!(simulate(&walk, &hull("#####.##.##.#.###"), Mode::Walk), Some(11));
+
+        let run = compile("(!C & D & H) | (!B & D & H) | !A");
+        for tiles in WALKABLE.iter().chain(RUNNABLE.iter()) {
+            assert_eq!(simulate(&run, &hull(tiles), Mode::Run), None, "{}", tiles);
+        }
+
+        // no droid jumps a hole four tiles wide
+        assert_eq!(simulate(&run, &hull("#####....########"), Mode::Run), Some(8));
+        assert_eq!(simulate(&Vec::new(), &hull("#####.###########"), Mode::Walk), Some(5));
+    }
+
+    #[test]
+    fn springscript_files() {
+        let (code, mode) = parse_springscript("NOT A J\n\n  NOT C T\nAND D T\nOR T J\nWALK\n").unwrap();
+        assert_eq!(mode, Mode::Walk);
+        assert_eq!(code, compile("(!C & D) | !A"));
+
+        assert_eq!(parse_springscript("").unwrap_err(), "Springscript must end with WALK or RUN.");
+        assert_eq!(parse_springscript("NOT A J\n").unwrap_err(), "Springscript must end with WALK or RUN.");
+        assert_eq!(parse_springscript("NOT E J\nWALK").unwrap_err(), "Sensor E is not available in WALK mode.");
+        assert_eq!(parse_springscript("NOT E J\nRUN").unwrap().1, Mode::Run);
+        assert!(parse_springscript("JUMP\nRUN").is_err());
+    }
+
+    #[test]
+    fn death_animation() {
+        let output = "Input instructions:\n\nWalking...\n\n\nDidn't make it across:\n\n\
+            .................\n.................\n@................\n#####.#..########\n\n\
+            .................\n.................\n.@...............\n#####.#..########\n";
+
+        assert_eq!(parse_hull(output), Some(hull("#####.#..########")));
+        assert_eq!(parse_hull("Input instructions:\n#####.#..########\n"), None);
+    }
+
+    #[test]
+    fn search_crosses_known_hulls() {
+        let hulls = WALKABLE.iter().map(|tiles| hull(tiles)).collect::<Vec<Vec<bool>>>();
+        let code = search(&hulls, Mode::Walk).unwrap();
+        assert!(hulls.iter().all(|hull| simulate(&code, hull, Mode::Walk).is_none()));
+
+        let hulls = WALKABLE.iter().chain(RUNNABLE.iter()).map(|tiles| hull(tiles)).collect::<Vec<Vec<bool>>>();
+        let code = search(&hulls, Mode::Run).unwrap();
+        assert!(validate(&code, Mode::Run).is_ok());
+        assert!(hulls.iter().all(|hull| simulate(&code, hull, Mode::Run).is_none()));
+
+        assert_eq!(search(&vec![hull("#####....########")], Mode::Run), None);
+    }
+}
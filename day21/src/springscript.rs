@@ -0,0 +1,627 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+const MAX_INSTRUCTIONS: usize = 15;
+
+#[derive(Clone, Debug)]
+pub enum Expression {
+    Constant(bool),
+    Sensor(char),
+    Not(Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Expression::Constant(value) => write!(f, "{}", if *value { "1" } else { "0" }),
+            Expression::Sensor(sensor) => write!(f, "{}", sensor),
+            Expression::Not(inner) => write!(f, "!{}", inner),
+            Expression::And(left, right) => write!(f, "({} & {})", left, right),
+            Expression::Or(left, right) => write!(f, "({} | {})", left, right),
+        }
+    }
+}
+
+impl Expression {
+    fn sensors(&self, sensors: &mut BTreeSet<char>) {
+        match self {
+            Expression::Constant(_) => {}
+            Expression::Sensor(sensor) => {
+                sensors.insert(*sensor);
+            }
+            Expression::Not(inner) => inner.sensors(sensors),
+            Expression::And(left, right) | Expression::Or(left, right) => {
+                left.sensors(sensors);
+                right.sensors(sensors);
+            }
+        }
+    }
+
+    fn evaluate(&self, values: &HashMap<char, bool>) -> bool {
+        match self {
+            Expression::Constant(value) => *value,
+            Expression::Sensor(sensor) => values[sensor],
+            Expression::Not(inner) => !inner.evaluate(values),
+            Expression::And(left, right) => left.evaluate(values) && right.evaluate(values),
+            Expression::Or(left, right) => left.evaluate(values) || right.evaluate(values),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Operation {
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub source: char,
+    pub target: char,
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let operation = match self.operation {
+            Operation::And => "AND",
+            Operation::Or => "OR",
+            Operation::Not => "NOT",
+        };
+
+        write!(f, "{} {} {}", operation, self.source, self.target)
+    }
+}
+
+fn instruction(operation: Operation, source: char, target: char) -> Instruction {
+    Instruction { operation, source, target }
+}
+
+// expression := term ('|' term)*
+// term := factor ('&' factor)*
+// factor := '!' factor | '(' expression ')' | 'A'..='I'
+struct Parser {
+    tokens: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.tokens.get(self.position).cloned()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(token) if token == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(token) => Err(format!("Expected '{}' but found '{}' at {}.", expected, token, self.position)),
+            None => Err(format!("Expected '{}' but found end of expression.", expected)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        let mut expression = self.term()?;
+        while self.peek() == Some('|') {
+            self.position += 1;
+            expression = Expression::Or(Box::new(expression), Box::new(self.term()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn term(&mut self) -> Result<Expression, String> {
+        let mut expression = self.factor()?;
+        while self.peek() == Some('&') {
+            self.position += 1;
+            expression = Expression::And(Box::new(expression), Box::new(self.factor()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn factor(&mut self) -> Result<Expression, String> {
+        match self.peek() {
+            Some('!') => {
+                self.position += 1;
+                Ok(Expression::Not(Box::new(self.factor()?)))
+            }
+            Some('(') => {
+                self.position += 1;
+                let expression = self.expression()?;
+                self.expect(')')?;
+                Ok(expression)
+            }
+            Some(sensor) if ('A'..='I').contains(&sensor) => {
+                self.position += 1;
+                Ok(Expression::Sensor(sensor))
+            }
+            Some(token) => Err(format!("Unexpected '{}' at {}.", token, self.position)),
+            None => Err("Unexpected end of expression.".to_string()),
+        }
+    }
+}
+
+pub fn parse(source: &str) -> Result<Expression, String> {
+    let mut parser = Parser {
+        tokens: source.chars().filter(|c| !c.is_whitespace()).collect(),
+        position: 0,
+    };
+
+    let expression = parser.expression()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected '{}' at {}.", token, parser.position));
+    }
+
+    Ok(expression)
+}
+
+// An implicant is a product of literals: `mask` marks the variables it does
+// not depend on, `value` holds the required values of all the others.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Implicant {
+    value: u32,
+    mask: u32,
+}
+
+impl Implicant {
+    fn covers(&self, minterm: u32) -> bool {
+        minterm & !self.mask == self.value
+    }
+}
+
+// Quine-McCluskey: merge implicants that differ in a single variable until
+// nothing merges any more, then cover the minterms with the primes. Don't
+// cares take part in merging but never need to be covered.
+fn minimize(minterms: &[u32], dont_cares: &[u32]) -> Vec<Implicant> {
+    let mut current = minterms.iter().chain(dont_cares.iter()).map(|minterm| {
+        Implicant { value: *minterm, mask: 0 }
+    }).collect::<BTreeSet<_>>();
+    let mut primes = BTreeSet::new();
+
+    while !current.is_empty() {
+        let mut next = BTreeSet::new();
+        let mut merged = BTreeSet::new();
+        for implicant in &current {
+            for bit in 0..32 {
+                let difference = 1 << bit;
+                if implicant.mask & difference != 0 || implicant.value & difference != 0 {
+                    continue;
+                }
+
+                let partner = Implicant { value: implicant.value | difference, mask: implicant.mask };
+                if current.contains(&partner) {
+                    next.insert(Implicant { value: implicant.value, mask: implicant.mask | difference });
+                    merged.insert(*implicant);
+                    merged.insert(partner);
+                }
+            }
+        }
+
+        for implicant in &current {
+            if !merged.contains(implicant) {
+                primes.insert(*implicant);
+            }
+        }
+        current = next;
+    }
+
+    let mut uncovered = minterms.to_vec();
+    let mut cover = Vec::new();
+
+    // essential primes first, then greedily whatever covers the most
+    for minterm in minterms {
+        let covering = primes.iter().filter(|prime| prime.covers(*minterm)).collect::<Vec<_>>();
+        if covering.len() == 1 && !cover.contains(covering[0]) {
+            cover.push(*covering[0]);
+        }
+    }
+    uncovered.retain(|minterm| !cover.iter().any(|implicant| implicant.covers(*minterm)));
+
+    while !uncovered.is_empty() {
+        let best = *primes.iter().max_by_key(|prime| {
+            (uncovered.iter().filter(|minterm| prime.covers(**minterm)).count(), prime.mask.count_ones())
+        }).unwrap();
+        cover.push(best);
+        uncovered.retain(|minterm| !best.covers(*minterm));
+    }
+
+    cover
+}
+
+type Literal = (char, bool);
+
+fn literals(implicant: &Implicant, sensors: &[char]) -> Vec<Literal> {
+    sensors.iter().enumerate().filter(|(bit, _)| implicant.mask & (1 << bit) == 0).map(|(bit, sensor)| {
+        (*sensor, implicant.value & (1 << bit) != 0)
+    }).collect()
+}
+
+fn literal(literal: &Literal) -> Expression {
+    if literal.1 {
+        Expression::Sensor(literal.0)
+    } else {
+        Expression::Not(Box::new(Expression::Sensor(literal.0)))
+    }
+}
+
+fn combine(expressions: Vec<Expression>, conjunction: bool) -> Expression {
+    expressions.into_iter().fold(None, |combined, expression| {
+        match combined {
+            None => Some(expression),
+            Some(combined) if conjunction => Some(Expression::And(Box::new(combined), Box::new(expression))),
+            Some(combined) => Some(Expression::Or(Box::new(combined), Box::new(expression))),
+        }
+    }).unwrap_or(Expression::Constant(conjunction))
+}
+
+// Builds a two-level form (sum of products unless `outer_and` is set) and
+// pulls out literals shared by several terms.
+fn factor(terms: &Vec<Vec<Literal>>, outer_and: bool) -> Expression {
+    let mut counts = HashMap::<Literal, usize>::new();
+    for term in terms {
+        for literal in term {
+            *counts.entry(*literal).or_insert(0) += 1;
+        }
+    }
+
+    let shared = counts.iter().filter(|(_, count)| **count > 1).max_by_key(|(literal, count)| (**count, literal.0, literal.1));
+    match shared {
+        Some((shared, _)) => {
+            let (with, without): (Vec<_>, Vec<_>) = terms.iter().cloned().partition(|term| term.contains(shared));
+            let with = with.into_iter().map(|term| {
+                term.into_iter().filter(|literal| literal != shared).collect::<Vec<_>>()
+            }).collect::<Vec<_>>();
+
+            let factored = if with.iter().any(|term| term.is_empty()) {
+                literal(shared)
+            } else {
+                combine(vec![literal(shared), factor(&with, outer_and)], !outer_and)
+            };
+
+            if without.is_empty() {
+                factored
+            } else {
+                combine(vec![factored, factor(&without, outer_and)], outer_and)
+            }
+        }
+        None => flatten(terms, outer_and),
+    }
+}
+
+fn flatten(terms: &[Vec<Literal>], outer_and: bool) -> Expression {
+    combine(terms.iter().map(|term| {
+        combine(term.iter().map(literal).collect(), !outer_and)
+    }).collect(), outer_and)
+}
+
+// Bit `n` of a minterm is the value of `sensors[n]`.
+fn two_level(sensors: &[char], ones: &[u32], zeros: &[u32]) -> Vec<Expression> {
+    if ones.is_empty() {
+        return vec![Expression::Constant(false)];
+    }
+    if zeros.is_empty() {
+        return vec![Expression::Constant(true)];
+    }
+
+    let dont_cares = (0..(1u32 << sensors.len())).filter(|minterm| {
+        !ones.contains(minterm) && !zeros.contains(minterm)
+    }).collect::<Vec<u32>>();
+
+    let products = minimize(ones, &dont_cares).iter().map(|implicant| literals(implicant, sensors)).collect::<Vec<_>>();
+
+    // the product of sums comes from the complement by De Morgan
+    let sums = minimize(zeros, &dont_cares).iter().map(|implicant| {
+        literals(implicant, sensors).into_iter().map(|(sensor, value)| (sensor, !value)).collect()
+    }).collect::<Vec<_>>();
+
+    vec![
+        flatten(&products, false),
+        factor(&products, false),
+        flatten(&sums, true),
+        factor(&sums, true),
+    ]
+}
+
+fn candidates(expression: &Expression) -> Vec<Expression> {
+    let mut sensors = BTreeSet::new();
+    expression.sensors(&mut sensors);
+    let sensors = sensors.into_iter().collect::<Vec<char>>();
+
+    let (ones, zeros): (Vec<u32>, Vec<u32>) = (0..(1u32 << sensors.len())).partition(|minterm| {
+        let values = sensors.iter().enumerate().map(|(bit, sensor)| {
+            (*sensor, minterm & (1 << bit) != 0)
+        }).collect::<HashMap<char, bool>>();
+        expression.evaluate(&values)
+    });
+
+    let mut candidates = two_level(&sensors, &ones, &zeros);
+    if !ones.is_empty() && !zeros.is_empty() {
+        candidates.push(expression.clone());
+    }
+
+    candidates
+}
+
+fn other(register: char) -> char {
+    if register == 'J' { 'T' } else { 'J' }
+}
+
+fn as_sensor(expression: &Expression, polarity: bool) -> Option<char> {
+    match expression {
+        Expression::Sensor(sensor) if polarity => Some(*sensor),
+        Expression::Not(inner) => as_sensor(inner, !polarity),
+        _ => None,
+    }
+}
+
+fn shorter(best: Option<Vec<Instruction>>, candidate: Option<Vec<Instruction>>) -> Option<Vec<Instruction>> {
+    match (best, candidate) {
+        (Some(best), Some(candidate)) => if candidate.len() < best.len() { Some(candidate) } else { Some(best) },
+        (best, candidate) => best.or(candidate),
+    }
+}
+
+type Memo = HashMap<(String, bool, char, bool), Option<Vec<Instruction>>>;
+
+// Shortest code leaving the expression (or its negation, if `polarity` is
+// false) in `register`. The other register may only be clobbered if
+// `scratch` is set.
+fn generate(expression: &Expression, polarity: bool, register: char, scratch: bool, memo: &mut Memo) -> Option<Vec<Instruction>> {
+    let key = (expression.to_string(), polarity, register, scratch);
+    if let Some(code) = memo.get(&key) {
+        return code.clone();
+    }
+
+    let direct = generate_direct(expression, polarity, register, scratch, memo);
+    let inverted = generate_direct(expression, !polarity, register, scratch, memo).map(|mut code| {
+        code.push(instruction(Operation::Not, register, register));
+        code
+    });
+    let code = shorter(direct, inverted);
+
+    memo.insert(key, code.clone());
+    code
+}
+
+fn generate_direct(expression: &Expression, polarity: bool, register: char, scratch: bool, memo: &mut Memo) -> Option<Vec<Instruction>> {
+    match expression {
+        Expression::Constant(_) => None,
+        Expression::Sensor(sensor) => {
+            if polarity {
+                None
+            } else {
+                Some(vec![instruction(Operation::Not, *sensor, register)])
+            }
+        }
+        Expression::Not(inner) => generate(inner, !polarity, register, scratch, memo),
+        Expression::And(left, right) | Expression::Or(left, right) => {
+            // by De Morgan the negation flips the operation and negates both operands
+            let operation = match (expression, polarity) {
+                (Expression::And(_, _), true) | (Expression::Or(_, _), false) => Operation::And,
+                _ => Operation::Or,
+            };
+
+            let mut best = None;
+            for (first, second) in [(left, right), (right, left)] {
+                if let Some(sensor) = as_sensor(second, polarity) {
+                    let code = generate(first, polarity, register, scratch, memo).map(|mut code| {
+                        code.push(instruction(operation, sensor, register));
+                        code
+                    });
+                    best = shorter(best, code);
+                }
+
+                if scratch {
+                    let code = match (generate(first, polarity, register, true, memo), generate(second, polarity, other(register), false, memo)) {
+                        (Some(mut first), Some(second)) => {
+                            first.extend(second);
+                            first.push(instruction(operation, other(register), register));
+                            Some(first)
+                        }
+                        _ => None,
+                    };
+                    best = shorter(best, code);
+                }
+            }
+
+            best
+        }
+    }
+}
+
+// Both registers start out false, which lets a few sequences collapse.
+fn peephole(code: &[Instruction]) -> Vec<Instruction> {
+    let mut known = HashMap::new();
+    known.insert('T', Some(false));
+    known.insert('J', Some(false));
+
+    let mut optimized = Vec::new();
+    let mut index = 0;
+    while index < code.len() {
+        let current = code[index];
+        let target = current.target;
+
+        if current.operation == Operation::Not && current.source != target && index + 1 < code.len()
+            && code[index + 1] == instruction(Operation::Not, target, target) && known[&target].is_some() {
+            let operation = if known[&target] == Some(false) { Operation::Or } else { Operation::And };
+            optimized.push(instruction(operation, current.source, target));
+            known.insert(target, None);
+            index += 2;
+            continue;
+        }
+
+        let source = known.get(&current.source).cloned().flatten();
+        let value = match current.operation {
+            Operation::And if known[&target] == Some(false) || source == Some(true) => {
+                index += 1;
+                continue;
+            }
+            Operation::Or if known[&target] == Some(true) || source == Some(false) => {
+                index += 1;
+                continue;
+            }
+            Operation::Not => source.map(|value| !value),
+            Operation::And => if source == Some(false) { Some(false) } else { None },
+            Operation::Or => if source == Some(true) { Some(true) } else { None },
+        };
+
+        optimized.push(current);
+        known.insert(target, value);
+        index += 1;
+    }
+
+    optimized
+}
+
+fn shortest(candidates: Vec<Expression>) -> Result<Vec<Instruction>, String> {
+    let mut best: Option<Vec<Instruction>> = None;
+    for candidate in candidates {
+        let code = match candidate {
+            Expression::Constant(true) => Some(vec![instruction(Operation::Not, 'J', 'J')]),
+            Expression::Constant(false) => Some(Vec::new()),
+            _ => generate(&candidate, true, 'J', true, &mut Memo::new()).map(|code| peephole(&code)),
+        };
+        best = shorter(best, code);
+    }
+
+    match best {
+        Some(code) if code.len() <= MAX_INSTRUCTIONS => Ok(code),
+        Some(code) => Err(format!("Expression needs {} instructions, but only {} fit.", code.len(), MAX_INSTRUCTIONS)),
+        None => Err("Expression cannot be computed with two registers.".to_string()),
+    }
+}
+
+pub fn compile_expression(expression: &Expression) -> Result<Vec<Instruction>, String> {
+    shortest(candidates(expression))
+}
+
+// Compiles a partial truth table: `ones` and `zeros` list the sensor
+// readings that must (not) make the droid jump, anything else is free.
+pub fn compile_table(sensors: &[char], ones: &[u32], zeros: &[u32]) -> Result<Vec<Instruction>, String> {
+    shortest(two_level(sensors, ones, zeros))
+}
+
+pub fn compile(source: &str) -> Result<Vec<String>, String> {
+    let code = compile_expression(&parse(source)?)?;
+
+    Ok(code.iter().map(|instruction| instruction.to_string()).collect())
+}
+
+pub fn parse_instruction(line: &str) -> Result<Instruction, String> {
+    let tokens = line.split_whitespace().collect::<Vec<&str>>();
+    if tokens.len() != 3 {
+        return Err(format!("Malformed instruction '{}'.", line));
+    }
+
+    let operation = match tokens[0] {
+        "AND" => Operation::And,
+        "OR" => Operation::Or,
+        "NOT" => Operation::Not,
+        _ => return Err(format!("Unknown operation '{}'.", tokens[0])),
+    };
+
+    let register = |token: &str, writable: bool| {
+        match token.chars().collect::<Vec<char>>()[..] {
+            [register] if register == 'T' || register == 'J' => Ok(register),
+            [register] if !writable && ('A'..='I').contains(&register) => Ok(register),
+            _ => Err(format!("Invalid register '{}' in '{}'.", token, line)),
+        }
+    };
+
+    Ok(instruction(operation, register(tokens[1], false)?, register(tokens[2], true)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::springdroid::execute;
+
+    fn readings(sensors: u32) -> HashMap<char, bool> {
+        ('A'..='I').enumerate().map(|(bit, sensor)| (sensor, sensors & (1 << bit) != 0)).collect()
+    }
+
+    // Runs the compiled code on every reading of the nine sensors.
+    fn assert_truth_table(source: &str) {
+        let expression = parse(source).unwrap();
+        let code = compile_expression(&expression).unwrap();
+        assert!(code.len() <= MAX_INSTRUCTIONS);
+
+        for sensors in 0..(1 << 9) {
+            assert_eq!(execute(&code, sensors), expression.evaluate(&readings(sensors)), "{} with sensors {:09b}", source, sensors);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse("").unwrap_err(), "Unexpected end of expression.");
+        assert_eq!(parse("A &").unwrap_err(), "Unexpected end of expression.");
+        assert_eq!(parse("(A | B").unwrap_err(), "Expected ')' but found end of expression.");
+        assert_eq!(parse("(A | B C)").unwrap_err(), "Expected ')' but found 'C' at 4.");
+        assert_eq!(parse("A B").unwrap_err(), "Unexpected 'B' at 1.");
+        assert_eq!(parse("A | J").unwrap_err(), "Unexpected 'J' at 2.");
+        assert_eq!(parse("!(A & B)").unwrap().to_string(), "!(A & B)");
+
+        assert_eq!(parse_instruction("AND A J").unwrap(), instruction(Operation::And, 'A', 'J'));
+        assert_eq!(parse_instruction("NOT T T").unwrap(), instruction(Operation::Not, 'T', 'T'));
+        assert!(parse_instruction("AND A").is_err());
+        assert!(parse_instruction("XOR A J").is_err());
+        assert!(parse_instruction("OR A B").is_err());
+        assert!(parse_instruction("OR K J").is_err());
+    }
+
+    #[test]
+    fn compiled_truth_tables() {
+        assert_truth_table("(!C & D) | !A");
+        assert_truth_table("(!C & D & H) | (!B & D & H) | !A");
+        assert_truth_table("A");
+        assert_truth_table("!A");
+        assert_truth_table("A & !A");
+        assert_truth_table("A | !A");
+        assert_truth_table("!(A & B & C) & D");
+        assert_truth_table("(A | B) & (C | D) & (E | F)");
+        assert_truth_table("(A & B) | (C & D) | (E & I)");
+        assert_truth_table("(A & !B) | (!A & B)");
+    }
+
+    #[test]
+    fn too_many_instructions() {
+        // the parity of five sensors has no two minterms that merge
+        let parity = (0..32_u32).filter(|minterm| minterm.count_ones() % 2 == 1).map(|minterm| {
+            let product = ('A'..='E').enumerate().map(|(bit, sensor)| {
+                if minterm & (1 << bit) != 0 { sensor.to_string() } else { format!("!{}", sensor) }
+            }).collect::<Vec<String>>();
+            format!("({})", product.join(" & "))
+        }).collect::<Vec<String>>().join(" | ");
+
+        let message = compile(&parity).unwrap_err();
+        assert!(message.starts_with("Expression needs "), "{}", message);
+        assert!(message.ends_with(&format!("but only {} fit.", MAX_INSTRUCTIONS)), "{}", message);
+    }
+
+    #[test]
+    fn peephole_rewrites() {
+        let not = |source, target| instruction(Operation::Not, source, target);
+        let and = |source, target| instruction(Operation::And, source, target);
+        let or = |source, target| instruction(Operation::Or, source, target);
+
+        // a double negation into a register known to be false is a copy
+        assert_eq!(peephole(&[not('A', 'J'), not('J', 'J')]), vec![or('A', 'J')]);
+        assert_eq!(peephole(&[not('B', 'T'), not('T', 'T'), and('T', 'J')]), vec![or('B', 'T')]);
+
+        // ...and into a register known to be true it is an AND
+        assert_eq!(peephole(&[not('J', 'J'), not('A', 'J'), not('J', 'J')]), vec![not('J', 'J'), and('A', 'J')]);
+
+        // AND into false and OR into true change nothing
+        assert_eq!(peephole(&[and('A', 'J'), or('B', 'J')]), vec![or('B', 'J')]);
+        assert_eq!(peephole(&[not('T', 'J'), or('A', 'J'), and('T', 'J')]), vec![not('T', 'J'), and('T', 'J')]);
+        assert_eq!(peephole(&[or('A', 'J'), or('T', 'J')]), vec![or('A', 'J')]);
+
+        // nothing is known about the registers after reading a sensor
+        assert_eq!(peephole(&[or('A', 'J'), and('B', 'J')]), vec![or('A', 'J'), and('B', 'J')]);
+    }
+}
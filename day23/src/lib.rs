@@ -0,0 +1,31 @@
+pub mod network;
+
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day23;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+fn answer(y: Option<i64>) -> String {
+    match y {
+        Some(y) => y.to_string(),
+        None => "Network stalled.".to_string(),
+    }
+}
+
+impl Solution for Day23 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(answer(network::Network::new(&parse(input)?, 50).simulate(&mut network::FirstPacket)))
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(answer(network::Network::new(&parse(input)?, 50).simulate(&mut network::RepeatedWake::default())))
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
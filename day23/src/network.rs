@@ -0,0 +1,308 @@
+use intcode::run;
+use intcode::Context;
+
+pub const NAT_ADDRESS: i64 = 255;
+
+#[derive(Debug, Clone)]
+pub struct Packet {
+    pub round: usize,
+    pub source: i64,
+    pub destination: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NodeStats {
+    pub sent: usize,
+    pub received: usize,
+    pub blocked_rounds: usize,
+}
+
+pub enum Wake {
+    Send(i64, i64),
+    Stop(i64),
+    Stall,
+}
+
+// Decides what the NAT does with the packets addressed to it and how it
+// wakes up an idle network. Returning an answer stops the simulation.
+pub trait Nat {
+    fn receive(&mut self, packet: &Packet) -> Option<i64>;
+    fn wake(&mut self) -> Wake;
+}
+
+pub struct FirstPacket;
+
+impl Nat for FirstPacket {
+    fn receive(&mut self, packet: &Packet) -> Option<i64> {
+        Some(packet.y)
+    }
+
+    fn wake(&mut self) -> Wake {
+        Wake::Stall
+    }
+}
+
+#[derive(Default)]
+pub struct RepeatedWake {
+    memory: Option<(i64, i64)>,
+    last_y: Option<i64>,
+}
+
+impl Nat for RepeatedWake {
+    fn receive(&mut self, packet: &Packet) -> Option<i64> {
+        self.memory = Some((packet.x, packet.y));
+        None
+    }
+
+    fn wake(&mut self) -> Wake {
+        match self.memory {
+            Some((_, y)) if self.last_y == Some(y) => Wake::Stop(y),
+            Some((x, y)) => {
+                self.last_y = Some(y);
+                Wake::Send(x, y)
+            }
+            None => Wake::Stall,
+        }
+    }
+}
+
+pub struct Network {
+    nodes: Vec<Context>,
+    outputs: Vec<Vec<i64>>,
+    waiting: Vec<bool>,
+    pub round: usize,
+    pub log: Vec<Packet>,
+    pub dropped: Vec<Packet>,
+    pub stats: Vec<NodeStats>,
+    pub wakes: Vec<Packet>,
+}
+
+impl Network {
+    pub fn new(program: &[i64], size: usize) -> Network {
+        let nodes = (0..size).map(|address| {
+            let mut context = Context::new(program);
+            context.inputs.push_back(address as i64);
+            context
+        }).collect();
+
+        Network {
+            nodes,
+            outputs: vec![Vec::new(); size],
+            waiting: vec![false; size],
+            round: 0,
+            log: Vec::new(),
+            dropped: Vec::new(),
+            stats: vec![NodeStats::default(); size],
+            wakes: Vec::new(),
+        }
+    }
+
+    // Packets for an address without a node are dropped and kept apart for
+    // the report.
+    fn deliver(&mut self, packet: Packet) {
+        if packet.destination < 0 || packet.destination as usize >= self.nodes.len() {
+            self.dropped.push(packet);
+            return;
+        }
+
+        let destination = packet.destination as usize;
+        self.nodes[destination].inputs.push_back(packet.x);
+        self.nodes[destination].inputs.push_back(packet.y);
+        self.stats[destination].received += 1;
+        self.waiting[destination] = false;
+    }
+
+    // Every node runs until it either produces one output or blocks on
+    // input. Complete packets are routed as soon as they are assembled.
+    fn step(&mut self, nat: &mut dyn Nat) -> Option<i64> {
+        self.round += 1;
+
+        for source in 0..self.nodes.len() {
+            // a read without a packet waiting gets -1
+            let polled = self.nodes[source].blocked && self.nodes[source].inputs.is_empty();
+            if polled {
+                self.nodes[source].inputs.push_back(-1);
+            }
+
+            match run(&mut self.nodes[source]) {
+                Some(output) => {
+                    self.outputs[source].push(output);
+                    self.waiting[source] = false;
+                }
+                None => {
+                    let node = &self.nodes[source];
+                    if node.blocked {
+                        self.stats[source].blocked_rounds += 1;
+                    }
+
+                    // the node read -1 and went straight back to reading
+                    // without sending anything
+                    self.waiting[source] = polled && node.blocked && node.inputs.is_empty() && self.outputs[source].is_empty();
+                }
+            }
+
+            if self.outputs[source].len() == 3 {
+                let packet = Packet {
+                    round: self.round,
+                    source: source as i64,
+                    destination: self.outputs[source][0],
+                    x: self.outputs[source][1],
+                    y: self.outputs[source][2],
+                };
+                self.outputs[source].clear();
+                self.stats[source].sent += 1;
+                self.log.push(packet.clone());
+
+                if packet.destination == NAT_ADDRESS {
+                    if let Some(answer) = nat.receive(&packet) {
+                        return Some(answer);
+                    }
+                } else {
+                    self.deliver(packet);
+                }
+            }
+        }
+
+        None
+    }
+
+    // The network is idle once every node has halted or is blocked on input
+    // with nothing queued, having found no packet on its last read as well.
+    // A node that just booted or handled a packet is blocked too, but it has
+    // yet to show that an empty read leaves it with nothing to send.
+    pub fn idle(&self) -> bool {
+        self.nodes.iter().enumerate().all(|(address, node)| {
+            let halted = !node.blocked && node.program[node.pc] == 99;
+            let blocked = node.blocked && self.waiting[address] && node.inputs.is_empty() && self.outputs[address].is_empty();
+
+            halted || blocked
+        })
+    }
+
+    pub fn simulate(&mut self, nat: &mut dyn Nat) -> Option<i64> {
+        loop {
+            if let Some(answer) = self.step(nat) {
+                return Some(answer);
+            }
+
+            if self.idle() {
+                match nat.wake() {
+                    Wake::Send(x, y) => {
+                        let packet = Packet { round: self.round, source: NAT_ADDRESS, destination: 0, x, y };
+                        self.log.push(packet.clone());
+                        self.wakes.push(packet.clone());
+                        self.deliver(packet);
+                    }
+                    Wake::Stop(answer) => return Some(answer),
+                    Wake::Stall => return None,
+                }
+            }
+        }
+    }
+
+    pub fn report(&self) {
+        println!("packets:");
+        for packet in &self.log {
+            println!("{:>6} {:>3} -> {:<3} ({}, {})", packet.round, packet.source, packet.destination, packet.x, packet.y);
+        }
+
+        if !self.dropped.is_empty() {
+            println!("dropped:");
+            for packet in &self.dropped {
+                println!("{:>6} {:>3} -> {:<3} ({}, {})", packet.round, packet.source, packet.destination, packet.x, packet.y);
+            }
+        }
+
+        println!("nodes:");
+        println!("{:>4} {:>6} {:>8} {:>7}", "node", "sent", "received", "blocked");
+        for (address, stats) in self.stats.iter().enumerate() {
+            println!("{:>4} {:>6} {:>8} {:>7}", address, stats.sent, stats.received, stats.blocked_rounds);
+        }
+
+        // one row per wake-up, with a bar as long as the gap since the last one
+        println!("nat:");
+        let mut last_round = 0;
+        for packet in &self.wakes {
+            let gap = packet.round - last_round;
+            println!("{:>6} {} ({}, {})", packet.round, "#".repeat(std::cmp::min(gap, 60)), packet.x, packet.y);
+            last_round = packet.round;
+        }
+        println!("{} rounds", self.round);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A node that sends (`destination`, address + 10, address + 20) when it
+    // boots and then forwards every packet it receives to the NAT.
+    fn program(destination: i64) -> Vec<i64> {
+        vec![
+            3, 37,
+            1001, 37, 10, 38,
+            1001, 37, 20, 39,
+            104, destination,
+            4, 38,
+            4, 39,
+            3, 38,
+            1008, 38, -1, 40,
+            1005, 40, 16,
+            3, 39,
+            104, 255,
+            4, 38,
+            4, 39,
+            1105, 1, 16,
+            99,
+            0, 0, 0, 0,
+        ]
+    }
+
+    #[test]
+    fn idle_once_every_node_polled_in_vain() {
+        let mut network = Network::new(&program(NAT_ADDRESS), 2);
+        let mut nat = RepeatedWake::default();
+
+        // three rounds for the packets, one to block on the first read
+        for _ in 0..4 {
+            assert_eq!(network.step(&mut nat), None);
+            assert!(!network.idle());
+        }
+        assert!(network.nodes.iter().all(|node| node.blocked));
+
+        assert_eq!(network.step(&mut nat), None);
+        assert!(network.idle());
+
+        // a packet wakes its node up again
+        network.deliver(Packet { round: network.round, source: NAT_ADDRESS, destination: 1, x: 1, y: 2 });
+        assert!(!network.idle());
+    }
+
+    #[test]
+    fn nat_stops_on_repeated_y() {
+        let mut network = Network::new(&program(NAT_ADDRESS), 2);
+        assert_eq!(network.simulate(&mut FirstPacket), Some(20));
+
+        let mut network = Network::new(&program(NAT_ADDRESS), 2);
+        assert_eq!(network.simulate(&mut RepeatedWake::default()), Some(21));
+        assert_eq!(network.wakes.len(), 1);
+        assert_eq!((network.wakes[0].x, network.wakes[0].y), (11, 21));
+        assert_eq!(network.stats[0].received, 1);
+    }
+
+    #[test]
+    fn packets_to_unknown_addresses_are_dropped() {
+        let mut network = Network::new(&program(7), 2);
+        assert_eq!(network.simulate(&mut RepeatedWake::default()), None);
+
+        assert_eq!(network.dropped.iter().map(|packet| (packet.source, packet.destination)).collect::<Vec<_>>(), vec![(0, 7), (1, 7)]);
+        assert_eq!(network.log.len(), 2);
+        assert!(network.stats.iter().all(|stats| stats.received == 0));
+
+        let mut network = Network::new(&program(-1), 1);
+        assert_eq!(network.simulate(&mut FirstPacket), None);
+        assert_eq!(network.dropped.len(), 1);
+    }
+}
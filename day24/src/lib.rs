@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use geometry::Direction;
+use geometry::Grid;
+use geometry::Position;
+use parsing::ParseError;
+use solution::Solution;
+use visualize::Frame;
+use visualize::Rgb;
+
+pub struct Day24;
+
+fn display(grid: &Grid<u32>) {
+    let snapshot = grid.render(|tile| match tile {
+        0 => '.',
+        1 => '#',
+        _ => unreachable!(),
+    });
+
+    println!("{}\n---", snapshot);
+}
+
+fn palette(tile: &u32) -> Rgb {
+    match tile {
+        0 => [24, 32, 24],
+        _ => [120, 220, 60],
+    }
+}
+
+fn biodiversity(grid: &Grid<u32>) -> u32 {
+    let mut rating = 0;
+    let mut factor = 1;
+    for tile in grid.values() {
+        rating += tile * factor;
+        factor <<= 1;
+    }
+
+    return rating;
+}
+
+#[derive(Debug)]
+struct Tile {
+    level: i32,
+    position: Position,
+}
+
+impl Tile {
+    fn adjacent_tiles_finite(&self) -> Vec<Tile> {
+        self.position.neighbours().into_iter().filter(|position| {
+            position.x >= 0 && position.x < 5 && position.y >= 0 && position.y < 5
+        }).map(|position| Tile {
+            level: self.level,
+            position,
+        }).collect()
+    }
+
+    fn adjacent_tiles_infinite(&self) -> Vec<Tile> {
+        let mut tiles = vec!();
+
+        if self.position.x == 2 && self.position.y == 2 {
+            return tiles;
+        }
+
+        for direction in Direction::all() {
+            match direction {
+                Direction::Up => {
+                    match self.position.y {
+                        0 => {
+                            tiles.push(Tile {
+                                level: self.level - 1,
+                                position: Position { x: 2, y: 1 },
+                            });
+                        }
+                        3 => {
+                            match self.position.x {
+                                2 => {
+                                    for i in 0..5 {
+                                        tiles.push(Tile {
+                                            level: self.level + 1,
+                                            position: Position { x: i, y: 4 },
+                                        });
+                                    }                                    
+                                }
+                                _ => {
+                                    let stepped = self.position.step(&direction);
+                                    tiles.push(Tile {
+                                        level: self.level,
+                                        position: stepped,
+                                    });                
+                                }
+                            }
+                        }
+                        _ => {
+                            let stepped = self.position.step(&direction);
+                            tiles.push(Tile {
+                                level: self.level,
+                                position: stepped,
+                            });                
+                        }
+                    }
+                }
+                Direction::Right => {
+                    match self.position.x {
+                        4 => {
+                            tiles.push(Tile {
+                                level: self.level - 1,
+                                position: Position { x: 3, y: 2 },
+                            });
+                        }
+                        1 => {
+                            match self.position.y {
+                                2 => {
+                                    for i in 0..5 {
+                                        tiles.push(Tile {
+                                            level: self.level + 1,
+                                            position: Position { x: 0, y: i },
+                                        });
+                                    }                                    
+                                }
+                                _ => {
+                                    let stepped = self.position.step(&direction);
+                                    tiles.push(Tile {
+                                        level: self.level,
+                                        position: stepped,
+                                    });                
+                                }
+                            }
+                        }
+                        _ => {
+                            let stepped = self.position.step(&direction);
+                            tiles.push(Tile {
+                                level: self.level,
+                                position: stepped,
+                            });                
+                        }
+                    }
+                }
+                Direction::Down => {
+                    match self.position.y {
+                        4 => {
+                            tiles.push(Tile {
+                                level: self.level - 1,
+                                position: Position { x: 2, y: 3 },
+                            });
+                        }
+                        1 => {
+                            match self.position.x {
+                                2 => {
+                                    for i in 0..5 {
+                                        tiles.push(Tile {
+                                            level: self.level + 1,
+                                            position: Position { x: i, y: 0 },
+                                        });
+                                    }                                    
+                                }
+                                _ => {
+                                    let stepped = self.position.step(&direction);
+                                    tiles.push(Tile {
+                                        level: self.level,
+                                        position: stepped,
+                                    });                
+                                }
+                            }
+                        }
+                        _ => {
+                            let stepped = self.position.step(&direction);
+                            tiles.push(Tile {
+                                level: self.level,
+                                position: stepped,
+                            });                
+                        }
+                    }
+                }
+                Direction::Left => {
+                    match self.position.x {
+                        0 => {
+                            tiles.push(Tile {
+                                level: self.level - 1,
+                                position: Position { x: 1, y: 2 },
+                            });
+                        }
+                        3 => {
+                            match self.position.y {
+                                2 => {
+                                    for i in 0..5 {
+                                        tiles.push(Tile {
+                                            level: self.level + 1,
+                                            position: Position { x: 4, y: i },
+                                        });
+                                    }                                    
+                                }
+                                _ => {
+                                    let stepped = self.position.step(&direction);
+                                    tiles.push(Tile {
+                                        level: self.level,
+                                        position: stepped,
+                                    });                
+                                }
+                            }
+                        }
+                        _ => {
+                            let stepped = self.position.step(&direction);
+                            tiles.push(Tile {
+                                level: self.level,
+                                position: stepped,
+                            });                
+                        }
+                    }
+                }
+            }
+        }
+
+        tiles
+    }
+
+    fn adjacent_tiles(&self, infinite: bool) -> Vec<Tile> {
+        if infinite {
+            self.adjacent_tiles_infinite()
+        } else {
+            self.adjacent_tiles_finite()
+        }
+    }
+}
+
+fn run_timestep(mut eris: HashMap<i32, Grid<u32>>, infinite: bool) -> HashMap<i32, Grid<u32>> {
+    if infinite {
+        let min_level = eris.keys().min().unwrap().clone();
+        eris.insert(min_level - 1, Grid::new(5, 5, 0));
+
+        let max_level = eris.keys().max().unwrap().clone();
+        eris.insert(max_level + 1, Grid::new(5, 5, 0));
+
+        let mut new_eris = HashMap::new();
+        for (level, grid) in eris.iter() {
+            let new_grid = grid.map(|position, bugs| {
+                let adjacent_bugs: u32 = Tile {
+                    level: *level,
+                    position: position.clone()
+                }.adjacent_tiles(true).iter().map(|tile| {
+                    match eris.get(&tile.level) {
+                        Some(g) => g[&tile.position],
+                        None => 0,
+                    }
+                }).sum();
+                match bugs {
+                    0 => {
+                        if adjacent_bugs == 1 || adjacent_bugs == 2 {
+                            1
+                        } else {
+                            0
+                        }
+                    },
+                    1 => {
+                        if adjacent_bugs != 1 {
+                            0
+                        } else {
+                            1
+                        }
+                    },
+                    _ => unreachable!()
+                }
+            });
+        
+            new_eris.insert(*level, new_grid);
+        }
+
+        new_eris
+    } else {
+        if let Some(grid) = eris.get(&0) {
+            let new_grid = grid.map(|position, bugs| {
+                let adjacent_bugs: u32 = Tile {
+                    level: 0,
+                    position: position.clone()
+                }.adjacent_tiles(false).iter().map(|tile| grid[&tile.position]).sum();
+                match bugs {
+                    0 => {
+                        if adjacent_bugs == 1 || adjacent_bugs == 2 {
+                            1
+                        } else {
+                            0
+                        }
+                    },
+                    1 => {
+                        if adjacent_bugs != 1 {
+                            0
+                        } else {
+                            1
+                        }
+                    },
+                    _ => unreachable!()
+                }
+            });
+        
+            eris.insert(0, new_grid);
+        }
+        eris
+    }
+}
+
+fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    let level0 = Grid::try_parse(input, |_position, character| match character {
+        '.' => Some(0),
+        '#' => Some(1),
+        _ => None,
+    })?;
+
+    if level0.width() != 5 || level0.height() != 5 {
+        return Err(ParseError::new(1, 1, &format!("expected a 5x5 map, found {}x{}", level0.width(), level0.height())));
+    }
+
+    // display(&level0);
+    return Ok(level0);
+}
+
+// Lets the bugs on a single level live until a layout comes up for the second
+// time and returns that layout. Every layout on the way goes to `visit`.
+fn first_repeated_layout<F>(level0: &Grid<u32>, mut visit: F) -> Grid<u32> where F: FnMut(&Grid<u32>) {
+    let mut eris = HashMap::new();
+    eris.insert(0, level0.clone());
+    let mut history = HashSet::new();
+    loop {
+        let layout = eris.get(&0).unwrap();
+        visit(layout);
+        if !history.insert(biodiversity(layout)) {
+            break;
+        }
+
+        eris = run_timestep(eris, false);
+    }
+
+    eris.remove(&0).unwrap()
+}
+
+// The number of bugs on all levels of the recursive grid after `minutes`.
+fn recursive_bugs(level0: &Grid<u32>, minutes: usize) -> u32 {
+    let mut eris = HashMap::new();
+    eris.insert(0, level0.clone());
+    for _step in 0..minutes {
+        eris = run_timestep(eris, true);
+    }
+
+    eris.values().map(|grid| grid.values().sum::<u32>()).sum()
+}
+
+impl Solution for Day24 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(biodiversity(&first_repeated_layout(&parse(input)?, |_layout| {})).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(recursive_bugs(&parse(input)?, 200).to_string())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_level0| ())
+    }
+
+    // One frame per minute on a single level, up to the first repeated layout.
+    fn visualize(&self, input: &str, frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        first_repeated_layout(&parse(input)?, |layout| frame(Frame::from_grid(layout, palette)));
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(recursive_bugs(&parse(include_str!("../examples/initial.txt")).unwrap(), 10), 99);
+    }
+}
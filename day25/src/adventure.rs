@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use intcode::Context;
+
+use crate::ints2string;
+use crate::run;
+use crate::string2ints;
+
+// Taking any of these ends the game or traps the droid for good.
+const DANGEROUS_ITEMS: [&str; 5] = [
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+
+struct Room {
+    name: String,
+    doors: Vec<String>,
+    items: Vec<String>,
+}
+
+// The droid may pass through several rooms in response to one command, the
+// last one described is where it ended up.
+fn parse_room(output: &str) -> Option<Room> {
+    let start = output.rfind("== ")?;
+    let mut lines = output[start..].lines();
+    let name = lines.next()?.trim_matches(|c| c == '=' || c == ' ').to_string();
+
+    let mut doors = Vec::new();
+    let mut items = Vec::new();
+    let mut list = None;
+    for line in lines {
+        match line {
+            "Doors here lead:" => list = Some(&mut doors),
+            "Items here:" => list = Some(&mut items),
+            _ => match (line.strip_prefix("- "), list.as_mut()) {
+                (Some(entry), Some(list)) => list.push(entry.to_string()),
+                _ => list = None,
+            },
+        }
+    }
+
+    Some(Room { name, doors, items })
+}
+
+fn opposite(door: &str) -> &'static str {
+    match door {
+        "north" => "south",
+        "south" => "north",
+        "east" => "west",
+        "west" => "east",
+        _ => unreachable!(),
+    }
+}
+
+struct Droid {
+    context: Context,
+    halted: bool,
+}
+
+impl Droid {
+    fn new(program: &Vec<i64>) -> Droid {
+        Droid { context: Context::new(program), halted: false }
+    }
+
+    // Sends a command and returns everything printed until the program asks
+    // for the next one or halts.
+    fn command(&mut self, command: &str) -> String {
+        self.context.inputs.extend(string2ints(&format!("{}\n", command)));
+        self.output()
+    }
+
+    fn output(&mut self) -> String {
+        let mut outputs = Vec::new();
+        while let Some(output) = run(&mut self.context) {
+            outputs.push(output);
+        }
+        self.halted = !self.context.blocked;
+
+        ints2string(&outputs)
+    }
+}
+
+struct Explorer {
+    droid: Droid,
+    visited: HashSet<String>,
+    inventory: Vec<String>,
+    // doors from the hull breach to the checkpoint, and the door of the
+    // checkpoint that leads onto the pressure-sensitive floor
+    checkpoint: Option<(Vec<String>, String)>,
+}
+
+impl Explorer {
+    // Visits every room reachable from the current one, picks up every
+    // harmless item and comes back.
+    fn explore(&mut self, room: &Room, path: &mut Vec<String>) {
+        self.visited.insert(room.name.clone());
+
+        for item in &room.items {
+            if !DANGEROUS_ITEMS.contains(&item.as_str()) {
+                self.droid.command(&format!("take {}", item));
+                self.inventory.push(item.clone());
+            }
+        }
+
+        for door in &room.doors {
+            let output = self.droid.command(door);
+            let next = parse_room(&output).unwrap();
+
+            // the floor sends the droid right back to the checkpoint
+            if next.name == room.name {
+                self.checkpoint = Some((path.clone(), door.clone()));
+                continue;
+            }
+
+            if !self.visited.contains(&next.name) {
+                path.push(door.clone());
+                self.explore(&next, path);
+                path.pop();
+            }
+            self.droid.command(opposite(door));
+        }
+    }
+}
+
+// Tries the item combinations in Gray code order, so every attempt takes or
+// drops a single item.
+fn pass_checkpoint(droid: &mut Droid, inventory: &Vec<String>, floor: &str) -> Option<String> {
+    let mut carried = (1u32 << inventory.len()) - 1;
+    for step in 0..(1u32 << inventory.len()) {
+        let combination = step ^ (step >> 1);
+        for (index, item) in inventory.iter().enumerate() {
+            let bit = 1 << index;
+            if combination & bit != carried & bit {
+                let verb = if combination & bit != 0 { "take" } else { "drop" };
+                droid.command(&format!("{} {}", verb, item));
+            }
+        }
+        carried = combination;
+
+        let output = droid.command(floor);
+        if droid.halted {
+            return Some(output);
+        }
+    }
+
+    None
+}
+
+// Plays the game until the droid is let through the checkpoint and returns
+// the password for the airlock.
+pub fn password(program: &Vec<i64>) -> Option<String> {
+    let mut droid = Droid::new(program);
+    let start = parse_room(&droid.output())?;
+
+    let mut explorer = Explorer { droid, visited: HashSet::new(), inventory: Vec::new(), checkpoint: None };
+    explorer.explore(&start, &mut Vec::new());
+
+    let (path, floor) = explorer.checkpoint?;
+    for door in &path {
+        explorer.droid.command(door);
+    }
+    let output = pass_checkpoint(&mut explorer.droid, &explorer.inventory, &floor)?;
+
+    let words = output.split_whitespace().collect::<Vec<&str>>();
+    words.iter().position(|word| *word == "typing").map(|index| words[index + 1].to_string())
+}
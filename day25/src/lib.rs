@@ -0,0 +1,41 @@
+mod adventure;
+
+use intcode::run;
+use parsing::ParseError;
+use solution::Solution;
+
+pub struct Day25;
+
+pub fn string2ints(string: &str) -> Vec<i64> {
+    string.as_bytes().iter().map(|byte| *byte as i64).collect::<Vec<_>>()
+}
+
+pub fn ints2string(ints: &Vec<i64>) -> String {
+    let bytes = ints.iter().map(|int| *int as u8).collect::<Vec<_>>();
+    match String::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(_) => String::new(),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    parsing::first_line(input)?.numbers(",")
+}
+
+impl Solution for Day25 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        match adventure::password(&parse(input)?) {
+            Some(password) => Ok(password),
+            None => Ok("The droid did not get past the checkpoint.".to_string()),
+        }
+    }
+
+    // Day 25 has no second puzzle.
+    fn part2(&self, _input: &str) -> Result<String, ParseError> {
+        Ok(String::new())
+    }
+
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_program| ())
+    }
+}
@@ -1,220 +1,53 @@
-use std::collections::HashMap;
-use std::collections::VecDeque;
-use std::io::Read;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::fs::File;
+use std::path::PathBuf;
 
-#[derive(Debug)]
-struct Context {
-    program: Vec<i64>,
-    pc: usize,
-    inputs: VecDeque<i64>,
-    relative_base: usize,
-    heap: HashMap<usize, i64>,
-}
-
-fn read_helper(context: &mut Context, address: usize) -> i64 {
-    if address >= context.program.len() {
-        match context.heap.get(&address) {
-            Some(value) => {
-                return *value;
-            }
-            None => {
-                context.heap.insert(address, 0);
-                return 0;
-            }
-        }
-    } else {
-        return context.program[address];
-    }
-}
+use day25::ints2string;
+use day25::string2ints;
+use day25::Day25;
+use intcode::run;
+use intcode::Context;
+use solution::or_exit;
+use solution::Solution;
 
-fn write_helper(context: &mut Context, address: usize, value: i64) {
-    if address >= context.program.len() {
-        context.heap.insert(address, value);
-    } else {
-        context.program[address] = value;
-    }
+fn usage() -> ! {
+    eprintln!("usage: day25 [<program>]");
+    std::process::exit(1);
 }
 
-fn read(context: &mut Context, offset: u32) -> i64 {
-    let mode = context.program[context.pc] / (10i64.pow(offset + 1)) % 10;
-    // dbg!(mode);
+// Commands for the droid come from stdin, so the program is read from a file,
+// by default the one next to the day's sources.
+fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    let path = match args.len() {
+        1 => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(Day25.input_file()),
+        2 => PathBuf::from(&args[1]),
+        _ => usage(),
+    };
 
-    match mode {
-        0 => {
-            let address = context.program[context.pc + offset as usize] as usize;
-            return read_helper(context, address);
-        }
-        1 => {
-            return context.program[context.pc + offset as usize];
+    let input = match std::fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", path.display(), error);
+            std::process::exit(1);
         }
-        2 => {
-            let address = context.program[context.pc + offset as usize];
-            return read_helper(context, (context.relative_base as i64 + address) as usize);
-        }
-       _ => panic!("Illegal mode flag."),
-    }
-}
-
-fn write(context: &mut Context, offset: u32, value: i64) {
-    let mode = context.program[context.pc] / (10i64.pow(offset + 1)) % 10;
+    };
+    let mut context = Context::new(&or_exit(day25::parse(&input)));
 
-    match mode {
-        0 => {
-            let address = context.program[context.pc + offset as usize] as usize;
-            write_helper(context, address, value);
-        }
-        2 => {
-            let address = context.program[context.pc + offset as usize];
-            write_helper(context, (context.relative_base as i64 + address) as usize, value);
+    // Part 1
+    loop {
+        let mut outputs = Vec::new();
+        while let Some(output) = run(&mut context) {
+            outputs.push(output);
         }
-       _ => panic!("Illegal mode flag."),
-    }
-}
-
-fn run(mut context: &mut Context) -> Option<i64> {
-    while context.program[context.pc] != 99 {
-        // println!("{}: {}", context.pc, context.program[context.pc]);
-        match context.program[context.pc] % 100 {
-            1 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-                write(&mut context, 3, operand1 + operand2);
-
-                context.pc += 4;
-            }
-            2 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-                write(&mut context, 3, operand1 * operand2);
-
-                context.pc += 4;
-            }
-            3 => {
-                let input = match context.inputs.pop_front() {
-                    Some(value) => value,
-                    None => unreachable!(),
-                };
-
-                write(&mut context, 1, input);
+        println!("{}", ints2string(&outputs));
 
-                context.pc += 2;
-            }
-            4 => {
-                let operand = read(&mut context, 1);
-
-                context.pc += 2;
-                return Some(operand);
-            }
-            5 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 != 0 {
-                    context.pc = operand2 as usize;
-                } else {
-                    context.pc += 3;
-                }
-            }
-            6 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 == 0 {
-                    context.pc = operand2 as usize;
-                } else {
-                    context.pc += 3;
-                }
-            }
-            7 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 < operand2 {
-                    write(&mut context, 3, 1);
-                } else {
-                    write(&mut context, 3, 0);
-                }
-                context.pc += 4;
-            }
-            8 => {
-                let operand1 = read(&mut context, 1);
-                let operand2 = read(&mut context, 2);
-
-                if operand1 == operand2 {
-                    write(&mut context, 3, 1);
-                } else {
-                    write(&mut context, 3, 0);
-                }
-                context.pc += 4;
-            }
-            9 => {
-                let operand = read(&mut context, 1);
-
-                context.relative_base = (context.relative_base as i64 + operand) as usize;
-                context.pc += 2;
-            }
-            _ => panic!("Illegal operation."),
+        if !context.blocked {
+            break;
         }
-    }
-
-    return None;
-}
-
-fn string2ints(string: &str) -> Vec<i64> {
-    string.as_bytes().iter().map(|byte| *byte as i64).collect::<Vec<_>>()
-}
 
-fn ints2string(ints: &Vec<i64>) -> String {
-    let bytes = ints.iter().map(|int| *int as u8).collect::<Vec<_>>();
-    match String::from_utf8(bytes) {
-        Ok(string) => string,
-        Err(_) => String::new(),
-    }
-}
-
-fn main() {
-    let file = File::open("input.txt").unwrap();
-    let mut reader = BufReader::new(&file);
-    let mut input = String::new();
-    reader.read_line(&mut input).unwrap();
-    input = input.trim_end().to_string();
-    let mut program = Vec::new();
-    for token in input.split(",") {
-        program.push(token.parse::<i64>().expect(token));
-    }
-
-    // Part 1
-    let mut context = Context {
-        program: program.clone(),
-        pc: 0,
-        inputs: VecDeque::new(),
-        relative_base: 0,
-        heap: HashMap::new(),
-    };
-
-    let mut outputs = Vec::new();
-    loop {
-        match run(&mut context) {
-            Some(output) => {
-                outputs.push(output);
-                if ints2string(&outputs.iter().rev().take(9).rev().cloned().collect::<Vec<i64>>()) == "Command?\n" {
-                    println!("{}", ints2string(&outputs));
-                    outputs.clear();
-                    let mut buffer = String::new();
-                    if let Ok(_) = std::io::stdin().read_line(&mut buffer) {
-                        // dbg!(&buffer);
-                        context.inputs.extend(string2ints(&buffer));
-                    }
-                }
-            }
-            None => {
-                println!("{}", ints2string(&outputs));
-                outputs.clear();
-                break;
-            }
-        };
+        let mut buffer = String::new();
+        match std::io::stdin().read_line(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => context.inputs.extend(string2ints(&buffer)),
+        }
     }
 }
@@ -0,0 +1,202 @@
+use std::ops::Index;
+use std::ops::IndexMut;
+
+use parsing::ParseError;
+
+use crate::Bounds;
+use crate::Position;
+
+// A dense rectangle of cells with its top left corner at the origin.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> where T: Clone {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == width), "Rows differ in length.");
+
+        Grid { width, height, cells: rows.into_iter().flatten().collect() }
+    }
+
+    // Builds a grid from a text map, one line per row, up to the first empty
+    // line.
+    pub fn parse<F>(text: &str, mut tile: F) -> Grid<T> where F: FnMut(&Position, char) -> T {
+        Grid::from_rows(text.lines().take_while(|line| !line.is_empty()).enumerate().map(|(y, line)| {
+            line.chars().enumerate().map(|(x, character)| {
+                tile(&Position { x: x as i64, y: y as i64 }, character)
+            }).collect()
+        }).collect())
+    }
+
+    // Like `parse`, but `tile` may reject a character by returning None, and
+    // every row must be as long as the first.
+    pub fn try_parse<F>(text: &str, mut tile: F) -> Result<Grid<T>, ParseError> where F: FnMut(&Position, char) -> Option<T> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for (y, line) in text.lines().take_while(|line| !line.is_empty()).enumerate() {
+            let mut row = Vec::new();
+            for (x, character) in line.chars().enumerate() {
+                match tile(&Position { x: x as i64, y: y as i64 }, character) {
+                    Some(value) => row.push(value),
+                    None => return Err(ParseError::new(y + 1, x + 1, &format!("unexpected `{}` in map", character))),
+                }
+            }
+
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    let message = format!("expected {} columns like the first row, found {}", first.len(), row.len());
+                    return Err(ParseError::new(y + 1, row.len() + 1, &message));
+                }
+            }
+            rows.push(row);
+        }
+
+        if rows.is_empty() {
+            return Err(ParseError::new(1, 1, "the map is empty"));
+        }
+
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: Position { x: 0, y: 0 },
+            max: Position { x: self.width as i64 - 1, y: self.height as i64 - 1 },
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.x >= 0 && position.y >= 0 && (position.x as usize) < self.width && (position.y as usize) < self.height
+    }
+
+    fn offset(&self, position: &Position) -> Option<usize> {
+        if self.contains(position) {
+            Some(position.y as usize * self.width + position.x as usize)
+        } else {
+            None
+        }
+    }
+
+    // Positions outside the grid have no cell.
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.offset(position).map(move |offset| &mut self.cells[offset])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        self.bounds().positions()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U> where F: FnMut(&Position, &T) -> U {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(position, cell)| f(&position, cell)).collect(),
+        }
+    }
+
+    pub fn render<F>(&self, tile: F) -> String where F: Fn(&T) -> char {
+        self.cells.chunks(self.width.max(1)).map(|row| {
+            row.iter().map(&tile).collect::<String>()
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+impl<T> Index<&Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: &Position) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the grid.", position),
+        }
+    }
+}
+
+impl<T> IndexMut<&Position> for Grid<T> {
+    fn index_mut(&mut self, position: &Position) -> &mut T {
+        match self.offset(position) {
+            Some(offset) => &mut self.cells[offset],
+            None => panic!("{:?} is outside the grid.", position),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_render() {
+        let mut start = None;
+        let grid = Grid::parse("#.#\n.@.\n\n", |position, character| {
+            if character == '@' {
+                start = Some(position.clone());
+            }
+            character == '#'
+        });
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(start, Some(Position::new(1, 1)));
+        assert_eq!(grid.get(&Position::new(2, 0)), Some(&true));
+        assert_eq!(grid.get(&Position::new(3, 0)), None);
+        assert_eq!(grid.get(&Position::new(0, -1)), None);
+        assert_eq!(grid.render(|wall| if *wall { '#' } else { '.' }), "#.#\n...");
+    }
+
+    #[test]
+    fn try_parse_reports_position() {
+        let tile = |_position: &Position, character| match character {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        assert_eq!(Grid::try_parse("#.\n.#\n", tile).map(|grid| grid.height()), Ok(2));
+        assert_eq!(Grid::try_parse("#.\n.x\n", tile), Err(ParseError::new(2, 2, "unexpected `x` in map")));
+        assert_eq!(
+            Grid::try_parse("#.\n.#.\n", tile),
+            Err(ParseError::new(2, 4, "expected 2 columns like the first row, found 3"))
+        );
+        assert_eq!(Grid::try_parse("\n#", tile), Err(ParseError::new(1, 1, "the map is empty")));
+    }
+
+    #[test]
+    fn map_and_index() {
+        let mut grid = Grid::new(4, 3, 0);
+        grid[&Position::new(3, 2)] = 5;
+        *grid.get_mut(&Position::new(0, 1)).unwrap() = 7;
+
+        let doubled = grid.map(|_position, value| value * 2);
+        assert_eq!(doubled[&Position::new(3, 2)], 10);
+        assert_eq!(doubled.values().sum::<i32>(), 24);
+        assert_eq!(grid.iter().find(|(_position, value)| **value == 7).unwrap().0, Position::new(0, 1));
+    }
+}
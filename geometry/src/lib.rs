@@ -0,0 +1,187 @@
+pub mod grid;
+pub mod sparse;
+
+pub use grid::Grid;
+pub use sparse::SparseGrid;
+
+// Screen coordinates: x grows to the right and y grows downwards.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Position {
+    pub fn new(x: i64, y: i64) -> Position {
+        Position { x, y }
+    }
+
+    pub fn step(&self, direction: &Direction) -> Position {
+        match direction {
+            Direction::Up => Position { x: self.x, y: self.y - 1 },
+            Direction::Right => Position { x: self.x + 1, y: self.y },
+            Direction::Down => Position { x: self.x, y: self.y + 1 },
+            Direction::Left => Position { x: self.x - 1, y: self.y },
+        }
+    }
+
+    // The four orthogonal neighbours, clockwise from the one above.
+    pub fn neighbours(&self) -> Vec<Position> {
+        Direction::all().iter().map(|direction| self.step(direction)).collect()
+    }
+
+    // All eight surrounding positions, clockwise from the one above.
+    pub fn neighbours_diagonal(&self) -> Vec<Position> {
+        [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)].iter().map(|(dx, dy)| {
+            Position { x: self.x + dx, y: self.y + dy }
+        }).collect()
+    }
+
+    pub fn manhattan_distance(&self, other: &Position) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn all() -> Vec<Direction> {
+        vec![Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+    }
+
+    pub fn left_hand_side(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn right_hand_side(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+// Smallest rectangle containing a set of positions, both corners included.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Bounds {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Bounds {
+    pub fn of<'a, I>(positions: I) -> Option<Bounds> where I: IntoIterator<Item = &'a Position> {
+        let mut positions = positions.into_iter();
+        let first = positions.next()?;
+
+        let mut bounds = Bounds { min: first.clone(), max: first.clone() };
+        for position in positions {
+            bounds.min.x = std::cmp::min(bounds.min.x, position.x);
+            bounds.min.y = std::cmp::min(bounds.min.y, position.y);
+            bounds.max.x = std::cmp::max(bounds.max.x, position.x);
+            bounds.max.y = std::cmp::max(bounds.max.y, position.y);
+        }
+
+        Some(bounds)
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.x >= self.min.x && position.x <= self.max.x && position.y >= self.min.y && position.y <= self.max.y
+    }
+
+    // The positions inside both rectangles, if there are any.
+    pub fn intersection(&self, other: &Bounds) -> Option<Bounds> {
+        let min = Position { x: self.min.x.max(other.min.x), y: self.min.y.max(other.min.y) };
+        let max = Position { x: self.max.x.min(other.max.x), y: self.max.y.min(other.max.y) };
+        if min.x > max.x || min.y > max.y {
+            return None;
+        }
+
+        Some(Bounds { min, max })
+    }
+
+    // Row by row, from the top left corner.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let (min, max) = (self.min.clone(), self.max.clone());
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Position { x, y }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_four_times_comes_back() {
+        for direction in Direction::all() {
+            let mut left = direction.clone();
+            let mut right = direction.clone();
+            for _ in 0..4 {
+                left = left.left_hand_side();
+                right = right.right_hand_side();
+            }
+            assert_eq!(left, direction);
+            assert_eq!(right, direction);
+            assert_eq!(direction.left_hand_side().left_hand_side(), direction.opposite());
+        }
+    }
+
+    #[test]
+    fn neighbours() {
+        let position = Position::new(2, 3);
+        assert_eq!(position.neighbours(), vec![
+            Position::new(2, 2), Position::new(3, 3), Position::new(2, 4), Position::new(1, 3),
+        ]);
+
+        let diagonal = position.neighbours_diagonal();
+        assert_eq!(diagonal.len(), 8);
+        assert!(diagonal.iter().all(|neighbour| neighbour.manhattan_distance(&position) <= 2));
+        assert!(position.neighbours().iter().all(|neighbour| diagonal.contains(neighbour)));
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(Bounds::of(&[]), None);
+
+        let bounds = Bounds::of(&[Position::new(3, -1), Position::new(-2, 4), Position::new(0, 0)]).unwrap();
+        assert_eq!(bounds, Bounds { min: Position::new(-2, -1), max: Position::new(3, 4) });
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert_eq!(bounds.positions().count(), 36);
+        assert_eq!(bounds.positions().next(), Some(Position::new(-2, -1)));
+        assert!(bounds.contains(&Position::new(3, 4)));
+        assert!(!bounds.contains(&Position::new(4, 4)));
+
+        let column = Bounds::of(&[Position::new(1, -5), Position::new(1, 5)]).unwrap();
+        assert_eq!(bounds.intersection(&column), Bounds::of(&[Position::new(1, -1), Position::new(1, 4)]));
+        assert_eq!(column.intersection(&Bounds::of(&[Position::new(2, 0)]).unwrap()), None);
+    }
+}
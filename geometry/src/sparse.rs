@@ -0,0 +1,114 @@
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use crate::Bounds;
+use crate::Position;
+
+// Cells at arbitrary positions, for maps that are discovered while exploring
+// and may extend in any direction.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.cells.contains_key(position)
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.cells.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.cells.get_mut(position)
+    }
+
+    pub fn insert(&mut self, position: Position, cell: T) -> Option<T> {
+        self.cells.insert(position, cell)
+    }
+
+    pub fn remove(&mut self, position: &Position) -> Option<T> {
+        self.cells.remove(position)
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, Position, T> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> hash_map::Keys<'_, Position, T> {
+        self.cells.keys()
+    }
+
+    pub fn values(&self) -> hash_map::Values<'_, Position, T> {
+        self.cells.values()
+    }
+
+    // None while the grid is empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::of(self.cells.keys())
+    }
+
+    // Renders the bounding box of all cells, `tile` gets None for the
+    // positions in between that have no cell.
+    pub fn render<F>(&self, tile: F) -> String where F: Fn(Option<&T>) -> char {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        (bounds.min.y..=bounds.max.y).map(|y| {
+            (bounds.min.x..=bounds.max.x).map(|x| tile(self.cells.get(&Position { x, y }))).collect::<String>()
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I>(cells: I) -> SparseGrid<T> where I: IntoIterator<Item = (Position, T)> {
+        SparseGrid { cells: cells.into_iter().collect() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Position, &'a T);
+    type IntoIter = hash_map::Iter<'a, Position, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_fills_gaps() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.render(|_cell| '#'), "");
+
+        grid.insert(Position::new(-1, -1), 'a');
+        grid.insert(Position::new(1, 0), 'b');
+        assert_eq!(grid.bounds().map(|bounds| (bounds.width(), bounds.height())), Some((3, 2)));
+        assert_eq!(grid.render(|cell| *cell.unwrap_or(&'.')), "a..\n..b");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::run;
+use crate::Context;
+
+// Anything that takes inputs and hands out outputs one at a time, so robot
+// controllers can be tried against canned outputs instead of a real program.
+pub trait Machine {
+    fn feed(&mut self, input: i64);
+
+    // None once the machine halted or waits for an input it does not have.
+    fn output(&mut self) -> Option<i64>;
+}
+
+impl Machine for Context {
+    fn feed(&mut self, input: i64) {
+        self.inputs.push_back(input);
+    }
+
+    fn output(&mut self) -> Option<i64> {
+        run(self)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Control {
+    Feed(Vec<i64>),
+    Stop,
+}
+
+// Feeds all inputs, then reads `count` outputs. Returns None if the machine
+// stops before it produced all of them.
+pub fn exchange<M>(machine: &mut M, inputs: &[i64], count: usize) -> Option<Vec<i64>> where M: Machine {
+    for input in inputs {
+        machine.feed(*input);
+    }
+
+    (0..count).map(|_| machine.output()).collect()
+}
+
+// Feeds `start`, then repeatedly reads `arity` outputs and hands them to the
+// controller, which decides on the next inputs. Ends when the controller
+// stops or the machine stops in the middle of a group, and returns the number
+// of groups the controller saw.
+pub fn drive<M, F>(machine: &mut M, start: &[i64], arity: usize, mut controller: F) -> usize where M: Machine, F: FnMut(&[i64]) -> Control {
+    let mut groups = 0;
+    let mut inputs = start.to_vec();
+    while let Some(outputs) = exchange(machine, &inputs, arity) {
+        groups += 1;
+        match controller(&outputs) {
+            Control::Feed(next) => inputs = next,
+            Control::Stop => break,
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    struct Replay {
+        outputs: VecDeque<i64>,
+        inputs: Vec<i64>,
+    }
+
+    impl Machine for Replay {
+        fn feed(&mut self, input: i64) {
+            self.inputs.push(input);
+        }
+
+        fn output(&mut self) -> Option<i64> {
+            self.outputs.pop_front()
+        }
+    }
+
+    fn replay(outputs: &[i64]) -> Replay {
+        Replay { outputs: outputs.iter().cloned().collect(), inputs: Vec::new() }
+    }
+
+    #[test]
+    fn drive_groups_outputs() {
+        let mut machine = replay(&[1, 2, 3, 4, 5]);
+        let mut seen = Vec::new();
+        let groups = drive(&mut machine, &[0], 2, |outputs| {
+            seen.push(outputs.to_vec());
+            Control::Feed(vec![outputs[0] + outputs[1]])
+        });
+
+        // the trailing 5 is not a complete group
+        assert_eq!(groups, 2);
+        assert_eq!(seen, vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(machine.inputs, vec![0, 3, 7]);
+    }
+
+    #[test]
+    fn drive_stops_on_request() {
+        let mut machine = replay(&[1, 2, 3]);
+        let groups = drive(&mut machine, &[], 1, |outputs| {
+            if outputs[0] == 2 { Control::Stop } else { Control::Feed(Vec::new()) }
+        });
+
+        assert_eq!(groups, 2);
+        assert_eq!(machine.outputs, vec![3]);
+    }
+
+    #[test]
+    fn exchange_with_intcode() {
+        // echoes every input doubled, forever
+        let program = vec![3, 9, 1002, 9, 2, 9, 4, 9, 1105, 1, 0];
+        let mut context = Context::new(&program);
+
+        assert_eq!(context.output(), None);
+        assert!(context.blocked);
+        assert_eq!(exchange(&mut context, &[21], 1), Some(vec![42]));
+        assert_eq!(exchange(&mut context, &[1, 2], 2), Some(vec![2, 4]));
+        assert_eq!(exchange(&mut context, &[], 1), None);
+    }
+
+    #[test]
+    fn drive_intcode_until_halt() {
+        // outputs its input and its square, and halts after a zero
+        let program = vec![3, 15, 4, 15, 2, 15, 15, 16, 4, 16, 1005, 15, 0, 99, 0, 0, 0];
+        let mut context = Context::new(&program);
+        let mut squares = Vec::new();
+        let groups = drive(&mut context, &[3], 2, |outputs| {
+            squares.push(outputs[1]);
+            Control::Feed(vec![outputs[0] - 1])
+        });
+
+        assert_eq!(groups, 4);
+        assert_eq!(squares, vec![9, 4, 1, 0]);
+        assert!(!context.blocked);
+    }
+}
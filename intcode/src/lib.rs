@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+pub mod driver;
+
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub program: Vec<i64>,
+    pub pc: usize,
+    pub inputs: VecDeque<i64>,
+    pub relative_base: usize,
+    pub heap: HashMap<usize, i64>,
+    pub blocked: bool,
+}
+
+impl Context {
+    pub fn new(program: &[i64]) -> Context {
+        Context {
+            program: program.to_vec(),
+            pc: 0,
+            inputs: VecDeque::new(),
+            relative_base: 0,
+            heap: HashMap::new(),
+            blocked: false,
+        }
+    }
+
+    // Memory as the program sees it, including the cells past its end.
+    pub fn peek(&self, address: usize) -> i64 {
+        match self.program.get(address) {
+            Some(value) => *value,
+            None => self.heap.get(&address).copied().unwrap_or(0),
+        }
+    }
+
+    pub fn poke(&mut self, address: usize, value: i64) {
+        write_helper(self, address, value);
+    }
+}
+
+fn read_helper(context: &mut Context, address: usize) -> i64 {
+    if address >= context.program.len() {
+        *context.heap.entry(address).or_insert(0)
+    } else {
+        context.program[address]
+    }
+}
+
+fn write_helper(context: &mut Context, address: usize, value: i64) {
+    if address >= context.program.len() {
+        context.heap.insert(address, value);
+    } else {
+        context.program[address] = value;
+    }
+}
+
+fn read(context: &mut Context, offset: u32) -> i64 {
+    let mode = context.program[context.pc] / (10i64.pow(offset + 1)) % 10;
+
+    match mode {
+        0 => {
+            let address = context.program[context.pc + offset as usize] as usize;
+            read_helper(context, address)
+        }
+        1 => context.program[context.pc + offset as usize],
+        2 => {
+            let address = context.program[context.pc + offset as usize];
+            read_helper(context, (context.relative_base as i64 + address) as usize)
+        }
+        _ => panic!("Illegal mode flag."),
+    }
+}
+
+fn write(context: &mut Context, offset: u32, value: i64) {
+    let mode = context.program[context.pc] / (10i64.pow(offset + 1)) % 10;
+
+    match mode {
+        0 => {
+            let address = context.program[context.pc + offset as usize] as usize;
+            write_helper(context, address, value);
+        }
+        2 => {
+            let address = context.program[context.pc + offset as usize];
+            write_helper(context, (context.relative_base as i64 + address) as usize, value);
+        }
+        _ => panic!("Illegal mode flag."),
+    }
+}
+
+pub fn run(context: &mut Context) -> Option<i64> {
+    context.blocked = false;
+    while context.program[context.pc] != 99 {
+        match context.program[context.pc] % 100 {
+            1 => {
+                let operand1 = read(context, 1);
+                let operand2 = read(context, 2);
+                write(context, 3, operand1 + operand2);
+
+                context.pc += 4;
+            }
+            2 => {
+                let operand1 = read(context, 1);
+                let operand2 = read(context, 2);
+                write(context, 3, operand1 * operand2);
+
+                context.pc += 4;
+            }
+            3 => {
+                // an empty input queue suspends the program right before the
+                // input instruction, so it can be resumed once fed
+                context.blocked = context.inputs.is_empty();
+                let input = context.inputs.pop_front()?;
+
+                write(context, 1, input);
+
+                context.pc += 2;
+            }
+            4 => {
+                let operand = read(context, 1);
+
+                context.pc += 2;
+                return Some(operand);
+            }
+            5 => {
+                let operand1 = read(context, 1);
+                let operand2 = read(context, 2);
+
+                if operand1 != 0 {
+                    context.pc = operand2 as usize;
+                } else {
+                    context.pc += 3;
+                }
+            }
+            6 => {
+                let operand1 = read(context, 1);
+                let operand2 = read(context, 2);
+
+                if operand1 == 0 {
+                    context.pc = operand2 as usize;
+                } else {
+                    context.pc += 3;
+                }
+            }
+            7 => {
+                let operand1 = read(context, 1);
+                let operand2 = read(context, 2);
+
+                if operand1 < operand2 {
+                    write(context, 3, 1);
+                } else {
+                    write(context, 3, 0);
+                }
+                context.pc += 4;
+            }
+            8 => {
+                let operand1 = read(context, 1);
+                let operand2 = read(context, 2);
+
+                if operand1 == operand2 {
+                    write(context, 3, 1);
+                } else {
+                    write(context, 3, 0);
+                }
+                context.pc += 4;
+            }
+            9 => {
+                let operand = read(context, 1);
+
+                context.relative_base = (context.relative_base as i64 + operand) as usize;
+                context.pc += 2;
+            }
+            _ => panic!("Illegal operation."),
+        }
+    }
+
+    None
+}
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+// The block letters used by the puzzles are six pixels high and (mostly) four
+// pixels wide, with at least one blank column between two letters.
+const FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// Drops blank columns on either side so glyphs can be compared regardless of
+// how they sit in their cell.
+fn trim(rows: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let used = (0..width).filter(|x| {
+        rows.iter().any(|row| *row.get(*x).unwrap_or(&false))
+    }).collect::<Vec<usize>>();
+
+    match (used.first(), used.last()) {
+        (Some(first), Some(last)) => {
+            rows.iter().map(|row| {
+                (*first..=*last).map(|x| *row.get(x).unwrap_or(&false)).collect()
+            }).collect()
+        }
+        _ => rows.iter().map(|_| Vec::new()).collect(),
+    }
+}
+
+fn recognize(glyph: &[Vec<bool>]) -> Option<char> {
+    let glyph = trim(glyph);
+
+    FONT.iter().find_map(|(letter, pattern)| {
+        let pattern = pattern.iter().map(|row| {
+            row.chars().map(|pixel| pixel == '#').collect()
+        }).collect::<Vec<Vec<bool>>>();
+
+        if trim(&pattern) == glyph {
+            Some(*letter)
+        } else {
+            None
+        }
+    })
+}
+
+pub fn decode_grid<T, F>(grid: &[Vec<T>], lit: F) -> Option<String> where F: Fn(&T) -> bool {
+    let rows = grid.iter().map(|row| {
+        row.iter().map(&lit).collect()
+    }).collect::<Vec<Vec<bool>>>();
+
+    let rows = rows.into_iter().skip_while(|row| !row.contains(&true)).collect::<Vec<Vec<bool>>>();
+    let height = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    if height != 6 {
+        return None;
+    }
+    let rows = &rows[..height];
+
+    let width = rows.iter().map(|row| row.len()).max()?;
+    let blank = (0..width).map(|x| {
+        rows.iter().all(|row| !*row.get(x).unwrap_or(&false))
+    }).collect::<Vec<bool>>();
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank[x] {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !blank[x] {
+            x += 1;
+        }
+
+        let glyph = rows.iter().map(|row| {
+            (start..x).map(|x| *row.get(x).unwrap_or(&false)).collect()
+        }).collect::<Vec<Vec<bool>>>();
+        text.push(recognize(&glyph)?);
+    }
+
+    Some(text)
+}
+
+pub fn decode_points<I>(points: I) -> Option<String> where I: IntoIterator<Item = (i64, i64)> {
+    let points = points.into_iter().collect::<HashSet<(i64, i64)>>();
+
+    let min_x = points.iter().map(|point| point.0).min()?;
+    let max_x = points.iter().map(|point| point.0).max()?;
+    let min_y = points.iter().map(|point| point.1).min()?;
+    let max_y = points.iter().map(|point| point.1).max()?;
+
+    let grid = (min_y..=max_y).map(|y| {
+        (min_x..=max_x).map(|x| points.contains(&(x, y))).collect()
+    }).collect::<Vec<Vec<bool>>>();
+
+    decode_grid(&grid, |pixel| *pixel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Renders the letters with a blank column after each one, which puts the
+    // four pixel wide letters into five pixel wide cells as in the puzzles.
+    fn render(text: &str) -> Vec<Vec<bool>> {
+        (0..6).map(|y| {
+            text.chars().flat_map(|letter| {
+                let pattern = FONT.iter().find(|(candidate, _)| *candidate == letter).unwrap().1;
+                let mut row = pattern[y].chars().map(|pixel| pixel == '#').collect::<Vec<bool>>();
+                row.push(false);
+                row
+            }).collect()
+        }).collect()
+    }
+
+    fn points(grid: &[Vec<bool>], offset: (i64, i64)) -> Vec<(i64, i64)> {
+        grid.iter().enumerate().flat_map(|(y, row)| {
+            row.iter().enumerate().filter(|(_, lit)| **lit).map(move |(x, _)| (x as i64 + offset.0, y as i64 + offset.1))
+        }).collect()
+    }
+
+    #[test]
+    fn every_glyph() {
+        for (letter, _) in FONT.iter() {
+            let grid = render(&letter.to_string());
+
+            assert_eq!(decode_grid(&grid, |pixel| *pixel), Some(letter.to_string()));
+            assert_eq!(decode_points(points(&grid, (-7, 3))), Some(letter.to_string()));
+        }
+
+        let text = FONT.iter().map(|(letter, _)| *letter).collect::<String>();
+        let grid = render(&text);
+        assert_eq!(decode_grid(&grid, |pixel| *pixel), Some(text.clone()));
+        assert_eq!(decode_points(points(&grid, (0, 0))), Some(text));
+    }
+
+    #[test]
+    fn padding_is_ignored() {
+        let mut grid = vec![vec!['.'; 24]];
+        for row in render("ZEKU") {
+            let mut padded = vec!['.', '.'];
+            padded.extend(row.iter().map(|lit| if *lit { '#' } else { '.' }));
+            grid.push(padded);
+        }
+        grid.push(vec!['.'; 10]);
+
+        assert_eq!(decode_grid(&grid, |pixel| *pixel == '#'), Some("ZEKU".to_string()));
+    }
+
+    #[test]
+    fn unreadable() {
+        // a glyph that is not in the font
+        let mut grid = render("HI");
+        grid[0][1] = true;
+        assert_eq!(decode_grid(&grid, |pixel| *pixel), None);
+        assert_eq!(decode_points(points(&grid, (0, 0))), None);
+
+        // the width is not a multiple of the letter width, so the last
+        // letter is cut off
+        let grid = render("CAFE").into_iter().map(|mut row| {
+            row.truncate(17);
+            row
+        }).collect::<Vec<Vec<bool>>>();
+        assert_eq!(decode_grid(&grid, |pixel| *pixel), None);
+        assert_eq!(decode_points(points(&grid, (0, 0))), None);
+
+        // letters that touch are not told apart
+        let grid = render("LL").into_iter().map(|mut row| {
+            row.remove(4);
+            row
+        }).collect::<Vec<Vec<bool>>>();
+        assert_eq!(decode_grid(&grid, |pixel| *pixel), None);
+
+        // too short, and nothing at all
+        assert_eq!(decode_grid(&render("AB")[1..], |pixel| *pixel), None);
+        assert_eq!(decode_grid(&Vec::<Vec<bool>>::new(), |pixel| *pixel), None);
+        assert_eq!(decode_points(Vec::new()), None);
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+use std::str::FromStr;
+
+// Where the input stopped making sense. Lines and columns count from one, the
+// column counts characters, not bytes.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: &str) -> ParseError {
+        ParseError { line, column, message: message.to_string() }
+    }
+
+    // For input that ends before everything needed was read.
+    pub fn at_end(input: &str, message: &str) -> ParseError {
+        ParseError::new(input.lines().count() + 1, 1, message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Reads one line from left to right.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    text: &'a str,
+    line: usize,
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(text: &'a str, line: usize) -> Scanner<'a> {
+        Scanner { text, line, offset: 0 }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.text[..self.offset].chars().count() + 1
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn error(&self, message: &str) -> ParseError {
+        ParseError::new(self.line, self.column(), message)
+    }
+
+    // What comes next, for error messages.
+    fn found(&self) -> String {
+        match self.rest().chars().next() {
+            Some(character) => format!("`{}`", character),
+            None => "end of line".to_string(),
+        }
+    }
+
+    fn expected(&self, what: &str) -> ParseError {
+        self.error(&format!("expected {}, found {}", what, self.found()))
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    // Takes `token` if the line continues with it.
+    pub fn accept(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.offset += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.accept(token) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{}`", token)))
+        }
+    }
+
+    pub fn one_of(&mut self, choices: &str) -> Result<char, ParseError> {
+        match self.rest().chars().next() {
+            Some(character) if choices.contains(character) => {
+                self.offset += character.len_utf8();
+                Ok(character)
+            }
+            _ => Err(self.expected(&format!("one of `{}`", choices))),
+        }
+    }
+
+    // A run of letters, digits and underscores.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        let rest = self.rest();
+        let length = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.expected("a name"));
+        }
+
+        self.offset += length;
+        Ok(&rest[..length])
+    }
+
+    // An integer with an optional sign. Fails without consuming anything if
+    // there is none, or if it does not fit into `T`.
+    pub fn number<T>(&mut self) -> Result<T, ParseError> where T: FromStr {
+        let rest = self.rest();
+        let sign = if rest.starts_with('-') || rest.starts_with('+') { 1 } else { 0 };
+        let length = rest[sign..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - sign);
+        if length == 0 {
+            return Err(self.expected("a number"));
+        }
+
+        let number = &rest[..sign + length];
+        match number.parse::<T>() {
+            Ok(value) => {
+                self.offset += number.len();
+                Ok(value)
+            }
+            Err(_) => Err(self.error(&format!("number `{}` is out of range", number))),
+        }
+    }
+
+    // Numbers separated by `separator` up to the end of the line, as in an
+    // Intcode program.
+    pub fn numbers<T>(&mut self, separator: &str) -> Result<Vec<T>, ParseError> where T: FromStr {
+        let mut numbers = vec![self.number()?];
+        while self.accept(separator) {
+            numbers.push(self.number()?);
+        }
+        self.end()?;
+
+        Ok(numbers)
+    }
+
+    // Single digits up to the end of the line, at least one.
+    pub fn digits(&mut self) -> Result<Vec<u32>, ParseError> {
+        let mut digits = Vec::new();
+        while let Some(digit) = self.rest().chars().next().and_then(|c| c.to_digit(10)) {
+            digits.push(digit);
+            self.offset += 1;
+        }
+
+        if digits.is_empty() {
+            return Err(self.expected("a digit"));
+        }
+        self.end()?;
+
+        Ok(digits)
+    }
+
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(self.expected("end of line"))
+        }
+    }
+}
+
+// A scanner for every line that is not blank, without trailing whitespace.
+pub fn lines(input: &str) -> impl Iterator<Item = Scanner<'_>> {
+    input.lines().enumerate().filter(|(_index, text)| !text.trim().is_empty()).map(|(index, text)| {
+        Scanner::new(text.trim_end(), index + 1)
+    })
+}
+
+// Puzzles whose whole input is a single line.
+pub fn first_line(input: &str) -> Result<Scanner<'_>, ParseError> {
+    lines(input).next().ok_or_else(|| ParseError::new(1, 1, "the input is empty"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scanner_reads_tokens() {
+        let mut scanner = Scanner::new("<x=-1, y=+20> R8 ab_c", 1);
+
+        scanner.expect("<x=").unwrap();
+        assert_eq!(scanner.number::<i32>(), Ok(-1));
+        scanner.expect(", y=").unwrap();
+        assert_eq!(scanner.number::<i32>(), Ok(20));
+        scanner.expect(">").unwrap();
+        scanner.skip_whitespace();
+        assert_eq!(scanner.one_of("URDL"), Ok('R'));
+        assert_eq!(scanner.number::<u8>(), Ok(8));
+        assert!(!scanner.accept("x"));
+        scanner.skip_whitespace();
+        assert_eq!(scanner.word(), Ok("ab_c"));
+        assert!(scanner.end().is_ok());
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let input = "1,2,3\n\n4,x,6\n7,8";
+        let lines = lines(input).collect::<Vec<Scanner>>();
+        assert_eq!(lines.len(), 3);
+
+        assert_eq!(lines[0].clone().numbers::<i64>(","), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            lines[1].clone().numbers::<i64>(","),
+            Err(ParseError::new(3, 3, "expected a number, found `x`"))
+        );
+        assert_eq!(
+            lines[2].clone().numbers::<u8>(";"),
+            Err(ParseError::new(4, 2, "expected end of line, found `,`"))
+        );
+        assert_eq!(
+            Scanner::new("300", 2).number::<u8>(),
+            Err(ParseError::new(2, 1, "number `300` is out of range"))
+        );
+        assert_eq!(Scanner::new("12a", 1).digits(), Err(ParseError::new(1, 3, "expected end of line, found `a`")));
+        assert_eq!(first_line(" \n\n").err(), Some(ParseError::new(1, 1, "the input is empty")));
+        assert_eq!(ParseError::at_end(input, "missing").line, 5);
+        assert_eq!(ParseError::new(3, 4, "oops").to_string(), "line 3, column 4: oops");
+    }
+
+    #[test]
+    fn scanner_never_panics() {
+        for text in &["", "-", "+", "ä-1", "é,", "--5", "9999999999999999999999", "1,", ",1"] {
+            let mut scanner = Scanner::new(text, 1);
+            let _ = scanner.number::<i64>();
+            let _ = scanner.clone().numbers::<i64>(",");
+            let _ = scanner.clone().digits();
+            let _ = scanner.one_of("é");
+            let _ = scanner.word();
+            assert!(scanner.column() >= 1);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::ops::Add;
+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct Statistics {
+    // states taken off the frontier and expanded
+    pub expanded: usize,
+    // successors handed out, including the ones seen before
+    pub generated: usize,
+    pub max_frontier: usize,
+}
+
+// Everything a search reached, with the cheapest known cost and the state it
+// was reached from. The costs are final for every state a search expanded;
+// states still on the frontier when a goal was found may have an overestimate.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    start: S,
+    costs: HashMap<S, C>,
+    parents: HashMap<S, S>,
+    pub goal: Option<S>,
+    pub statistics: Statistics,
+}
+
+impl<S, C> Search<S, C> where S: Eq + Hash + Clone, C: Copy {
+    fn new(start: S, cost: C) -> Search<S, C> {
+        let mut costs = HashMap::new();
+        costs.insert(start.clone(), cost);
+
+        Search { start, costs, parents: HashMap::new(), goal: None, statistics: Statistics::default() }
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).cloned()
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    // States from the start up to and including `state`.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+
+        debug_assert!(path[0] == self.start);
+        Some(path)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.path(self.goal.as_ref()?)
+    }
+
+    pub fn reached(&self) -> impl Iterator<Item = (&S, &C)> {
+        self.costs.iter()
+    }
+}
+
+// Breadth-first search where every step costs one. Stops at the first state
+// `goal` accepts, or explores everything reachable if it accepts none.
+pub fn bfs<S, I, N, G>(start: S, mut successors: N, mut goal: G) -> Search<S, usize>
+where S: Eq + Hash + Clone, I: IntoIterator<Item = S>, N: FnMut(&S) -> I, G: FnMut(&S) -> bool {
+    let mut search = Search::new(start.clone(), 0);
+    let mut frontier = VecDeque::new();
+    frontier.push_back(start);
+
+    while let Some(state) = frontier.pop_front() {
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        search.statistics.expanded += 1;
+        let cost = search.costs[&state] + 1;
+        for next in successors(&state) {
+            search.statistics.generated += 1;
+            if !search.costs.contains_key(&next) {
+                search.costs.insert(next.clone(), cost);
+                search.parents.insert(next.clone(), state.clone());
+                frontier.push_back(next);
+            }
+        }
+
+        search.statistics.max_frontier = std::cmp::max(search.statistics.max_frontier, frontier.len());
+    }
+
+    search
+}
+
+struct Candidate<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+// BinaryHeap is a max-heap, so the lowest estimate has to compare greatest.
+// Among equal estimates the candidate that got further is preferred.
+impl<S, C> Ord for Candidate<S, C> where C: Ord {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate).then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<S, C> PartialOrd for Candidate<S, C> where C: Ord {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C> PartialEq for Candidate<S, C> where C: Ord {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C> Eq for Candidate<S, C> where C: Ord {}
+
+// A* search: `successors` yields each next state with the cost of the step
+// there, `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<S, C, I, N, H, G>(start: S, mut successors: N, mut heuristic: H, mut goal: G) -> Search<S, C>
+where
+    S: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+    N: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Candidate { estimate: heuristic(&start), cost: C::default(), state: start });
+
+    while let Some(Candidate { cost, state, .. }) = frontier.pop() {
+        // a cheaper way to this state was found after this one was queued
+        if cost > search.costs[&state] {
+            continue;
+        }
+
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        search.statistics.expanded += 1;
+        for (next, step) in successors(&state) {
+            search.statistics.generated += 1;
+            let next_cost = cost + step;
+            if search.costs.get(&next).is_none_or(|known| next_cost < *known) {
+                search.costs.insert(next.clone(), next_cost);
+                search.parents.insert(next.clone(), state.clone());
+                frontier.push(Candidate { estimate: next_cost + heuristic(&next), cost: next_cost, state: next });
+            }
+        }
+
+        search.statistics.max_frontier = std::cmp::max(search.statistics.max_frontier, frontier.len());
+    }
+
+    search
+}
+
+// Dijkstra's algorithm is A* without a heuristic.
+pub fn dijkstra<S, C, I, N, G>(start: S, successors: N, goal: G) -> Search<S, C>
+where
+    S: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+    N: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_state| C::default(), goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+#########
+#S..#...#
+#.#.#.#.#
+#.#...#.#
+#.#####.#
+#......G#
+#########";
+
+    fn find(character: char) -> (i64, i64) {
+        MAZE.lines().enumerate().find_map(|(y, line)| {
+            line.find(character).map(|x| (x as i64, y as i64))
+        }).unwrap()
+    }
+
+    fn open_neighbours(&(x, y): &(i64, i64)) -> Vec<(i64, i64)> {
+        let lines = MAZE.lines().collect::<Vec<&str>>();
+        vec![(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)].into_iter().filter(|(x, y)| {
+            lines[*y as usize].as_bytes()[*x as usize] != b'#'
+        }).collect()
+    }
+
+    fn is_path(path: &[(i64, i64)]) -> bool {
+        path.windows(2).all(|step| open_neighbours(&step[0]).contains(&step[1]))
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let goal = find('G');
+        let search = bfs(find('S'), open_neighbours, |state| *state == goal);
+
+        assert_eq!(search.goal, Some(goal));
+        assert_eq!(search.goal_cost(), Some(10));
+
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path[0], find('S'));
+        assert!(is_path(&path));
+    }
+
+    #[test]
+    fn bfs_explores_everything_without_goal() {
+        let search = bfs(find('S'), open_neighbours, |_state| false);
+
+        assert_eq!(search.goal, None);
+        assert_eq!(search.reached().count(), 24);
+        assert_eq!(search.statistics.expanded, 24);
+        assert_eq!(search.reached().map(|(_state, cost)| *cost).max(), Some(12));
+        assert_eq!(search.cost(&(0, 0)), None);
+        assert_eq!(search.path(&(0, 0)), None);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_detours() {
+        // the direct edge from a to d is more expensive than going around
+        let edges: HashMap<char, Vec<(char, u32)>> = vec![
+            ('a', vec![('b', 1), ('d', 10)]),
+            ('b', vec![('c', 2), ('e', 20)]),
+            ('c', vec![('d', 3)]),
+            ('d', vec![('e', 1)]),
+        ].into_iter().collect();
+        let successors = |state: &char| edges.get(state).cloned().unwrap_or_default();
+
+        let search = dijkstra('a', successors, |state| *state == 'e');
+        assert_eq!(search.goal_cost(), Some(7));
+        assert_eq!(search.goal_path(), Some(vec!['a', 'b', 'c', 'd', 'e']));
+
+        let search = dijkstra('a', successors, |_state| false);
+        assert_eq!(search.cost(&'d'), Some(6));
+        assert_eq!(search.cost(&'e'), Some(7));
+        assert_eq!(search.statistics.expanded, 5);
+    }
+
+    #[test]
+    fn astar_agrees_with_bfs_and_expands_less() {
+        let start = find('S');
+        let goal = find('G');
+        let successors = |state: &(i64, i64)| {
+            open_neighbours(state).into_iter().map(|next| (next, 1)).collect::<Vec<_>>()
+        };
+        let manhattan = |state: &(i64, i64)| (state.0 - goal.0).abs() + (state.1 - goal.1).abs();
+
+        let informed = astar(start, successors, manhattan, |state| *state == goal);
+        let uninformed = dijkstra(start, successors, |state| *state == goal);
+
+        assert_eq!(informed.goal_cost(), Some(10));
+        assert_eq!(uninformed.goal_cost(), Some(10));
+        assert!(is_path(&informed.goal_path().unwrap()));
+        assert!(informed.statistics.expanded < uninformed.statistics.expanded);
+    }
+}
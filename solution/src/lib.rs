@@ -0,0 +1,36 @@
+use parsing::ParseError;
+use visualize::Frame;
+
+// A day's puzzle as seen by the runner. Both parts get the whole content of
+// the input file and return the answer the way it should be printed, or where
+// the input could not be parsed.
+pub trait Solution {
+    fn part1(&self, input: &str) -> Result<String, ParseError>;
+    fn part2(&self, input: &str) -> Result<String, ParseError>;
+
+    // Parses the input without solving anything, so that bad input can be
+    // reported before any work is done. Must not panic on any input.
+    fn check(&self, input: &str) -> Result<(), ParseError>;
+
+    // The input file used when none is given, relative to the day's folder.
+    fn input_file(&self) -> &'static str {
+        "input.txt"
+    }
+
+    // Hands pictures of the puzzle's states to `frame`, in order, for days
+    // whose states are grids. Returns whether the day has anything to show.
+    fn visualize(&self, _input: &str, _frame: &mut dyn FnMut(Frame)) -> Result<bool, ParseError> {
+        Ok(false)
+    }
+}
+
+// For the days' own binaries: reports bad input instead of panicking.
+pub fn or_exit<T>(result: Result<T, ParseError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("bad input: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
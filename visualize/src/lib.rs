@@ -0,0 +1,184 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use geometry::Grid;
+use geometry::Position;
+use geometry::SparseGrid;
+
+pub mod png;
+
+// Red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+// An image with one pixel per grid cell until it gets scaled.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Frame {
+        Frame { width, height, pixels: vec![background; width * height] }
+    }
+
+    // The palette gives every tile its colour.
+    pub fn from_grid<T, F>(grid: &Grid<T>, palette: F) -> Frame where F: Fn(&T) -> Rgb {
+        Frame { width: grid.width(), height: grid.height(), pixels: grid.values().map(palette).collect() }
+    }
+
+    // Covers the bounds of the cells that are set. The palette also colours
+    // the gaps in between, and the single pixel of an empty grid.
+    pub fn from_sparse<T, F>(grid: &SparseGrid<T>, palette: F) -> Frame where F: Fn(Option<&T>) -> Rgb {
+        match grid.bounds() {
+            Some(bounds) => Frame {
+                width: bounds.width() as usize,
+                height: bounds.height() as usize,
+                pixels: bounds.positions().map(|position| palette(grid.get(&position))).collect(),
+            },
+            None => Frame::new(1, 1, palette(None)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // Pixels outside the frame are ignored, so that overlays can be drawn
+    // without checking the bounds first.
+    pub fn set(&mut self, position: &Position, color: Rgb) {
+        if position.x >= 0 && (position.x as usize) < self.width && position.y >= 0 && (position.y as usize) < self.height {
+            self.pixels[position.y as usize * self.width + position.x as usize] = color;
+        }
+    }
+
+    // Every pixel becomes a `scale` x `scale` block.
+    pub fn scaled(&self, scale: usize) -> Frame {
+        let mut pixels = Vec::with_capacity(self.pixels.len() * scale * scale);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let scaled_row = row.iter().flat_map(|pixel| std::iter::repeat_n(*pixel, scale)).collect::<Vec<Rgb>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&scaled_row);
+            }
+        }
+
+        Frame { width: self.width * scale, height: self.height * scale, pixels }
+    }
+
+    // Binary PPM, which most image viewers and ffmpeg read.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut image = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            image.extend_from_slice(pixel);
+        }
+
+        image
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode(self.width, self.height, &self.pixels)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+// Writes a sequence of frames into a directory, numbered in the order in
+// which they are recorded, so that they can be turned into an animation.
+pub struct Recorder {
+    directory: PathBuf,
+    format: Format,
+    scale: usize,
+    frames: usize,
+}
+
+impl Recorder {
+    pub fn new(directory: &Path, format: Format, scale: usize) -> io::Result<Recorder> {
+        std::fs::create_dir_all(directory)?;
+
+        Ok(Recorder { directory: directory.to_path_buf(), format, scale, frames: 0 })
+    }
+
+    pub fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        let frame = frame.scaled(self.scale);
+        let image = match self.format {
+            Format::Ppm => frame.to_ppm(),
+            Format::Png => frame.to_png(),
+        };
+
+        let path = self.directory.join(format!("frame_{:04}.{}", self.frames, self.format.extension()));
+        std::fs::write(path, image)?;
+        self.frames += 1;
+
+        Ok(())
+    }
+
+    // How many frames were written so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_follow_the_grid() {
+        let grid = Grid::from_rows(vec![vec![0, 1], vec![1, 0]]);
+        let frame = Frame::from_grid(&grid, |tile| if *tile == 1 { WHITE } else { BLACK }).scaled(2);
+
+        assert_eq!((frame.width(), frame.height()), (4, 4));
+        assert_eq!(frame.get(1, 1), Some(BLACK));
+        assert_eq!(frame.get(2, 1), Some(WHITE));
+        assert_eq!(frame.get(3, 3), Some(BLACK));
+        assert_eq!(frame.get(4, 0), None);
+        assert_eq!(&frame.to_ppm()[..11], b"P6\n4 4\n255\n");
+        assert_eq!(frame.to_ppm().len(), 11 + 4 * 4 * 3);
+
+        let mut sparse = SparseGrid::new();
+        sparse.insert(Position::new(-1, 5), 'x');
+        sparse.insert(Position::new(1, 5), 'x');
+        let frame = Frame::from_sparse(&sparse, |tile| if tile.is_some() { WHITE } else { BLACK });
+        assert_eq!((frame.width(), frame.height()), (3, 1));
+        assert_eq!(frame.get(1, 0), Some(BLACK));
+        assert_eq!(Frame::from_sparse(&SparseGrid::<char>::new(), |_tile| WHITE), Frame::new(1, 1, WHITE));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::Rgb;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Deflate's stored blocks hold at most this many bytes.
+const MAX_BLOCK: usize = 0xffff;
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in bytes {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// A zlib stream of stored blocks. The frames are small and mostly viewed once,
+// so compressing them is not worth a deflate implementation.
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks = data.chunks(MAX_BLOCK).collect::<Vec<&[u8]>>();
+    if blocks.is_empty() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    for (index, block) in blocks.iter().enumerate() {
+        let last = index + 1 == blocks.len();
+        stream.push(if last { 1 } else { 0 });
+        stream.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        stream.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+// An 8 bit RGB image without interlacing. Every row starts with filter type 0,
+// which leaves the pixels as they are.
+pub fn encode(width: usize, height: usize, pixels: &[Rgb]) -> Vec<u8> {
+    let mut header = Vec::new();
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut data = Vec::with_capacity(height * (1 + width * 3));
+    for row in pixels.chunks(width.max(1)) {
+        data.push(0);
+        for pixel in row {
+            data.extend_from_slice(pixel);
+        }
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib(&data));
+    chunk(&mut png, b"IEND", &[]);
+
+    png
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn stored_blocks_cover_the_data() {
+        let data = (0..MAX_BLOCK + 10).map(|index| index as u8).collect::<Vec<u8>>();
+        let stream = zlib(&data);
+
+        assert_eq!(stream.len(), 2 + 5 + MAX_BLOCK + 5 + 10 + 4);
+        assert_eq!(&stream[2..7], &[0, 0xff, 0xff, 0, 0]);
+        assert_eq!(&stream[7 + MAX_BLOCK..12 + MAX_BLOCK], &[1, 10, 0, 0xf5, 0xff]);
+        assert_eq!(&stream[stream.len() - 4..], &adler32(&data).to_be_bytes());
+    }
+
+    #[test]
+    fn png_layout() {
+        let png = encode(2, 1, &[[255, 0, 0], [0, 0, 255]]);
+
+        assert_eq!(&png[..8], &SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+        assert_eq!(&png[png.len() - 12..], &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+    }
+}